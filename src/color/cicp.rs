@@ -0,0 +1,302 @@
+use super::{
+    ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, LumaCoefficients, RgbColorInfo,
+    YuvColorInfo,
+};
+
+/// ITU-T H.273 coding-independent code points as signaled in H.264/HEVC VUI, AV1 sequence headers,
+/// MP4 `colr` (nclx) boxes or PNG `cICP` chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cicp {
+    /// `ColourPrimaries`
+    pub color_primaries: u8,
+    /// `TransferCharacteristics`
+    pub transfer_characteristics: u8,
+    /// `MatrixCoefficients`
+    pub matrix_coefficients: u8,
+    /// `VideoFullRangeFlag`
+    pub full_range: bool,
+}
+
+/// Code point used by H.273 for "unspecified" in all three characteristics
+const UNSPECIFIED: u8 = 2;
+
+/// Error returned when trying to convert [`Cicp`] code points that are reserved or not supported into [`ColorInfo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum CicpError {
+    #[error("unsupported color primaries code point {0}")]
+    UnsupportedColorPrimaries(u8),
+
+    #[error("unsupported transfer characteristics code point {0}")]
+    UnsupportedTransferCharacteristics(u8),
+
+    #[error("unsupported matrix coefficients code point {0}")]
+    UnsupportedMatrixCoefficients(u8),
+
+    #[error("ICtCp requires PQ (16) or HLG (18) transfer characteristics, got {0}")]
+    InvalidICtCpTransfer(u8),
+}
+
+impl ColorPrimaries {
+    /// Returns the primaries for the given H.273 `ColourPrimaries` code point
+    ///
+    /// Unspecified (2) is treated as BT.709.
+    pub fn from_cicp(code_point: u8) -> Result<Self, CicpError> {
+        match code_point {
+            1 | UNSPECIFIED => Ok(Self::BT709),
+            6 | 7 => Ok(Self::ST240),
+            9 => Ok(Self::BT2020),
+            _ => Err(CicpError::UnsupportedColorPrimaries(code_point)),
+        }
+    }
+
+    /// Returns the H.273 `ColourPrimaries` code point, or unspecified (2) if there is none
+    pub fn to_cicp(self) -> u8 {
+        match self {
+            Self::ST240 => 7,
+            Self::BT709 => 1,
+            Self::BT2020 => 9,
+            Self::SGamut3
+            | Self::SGamut3Cine
+            | Self::ArriWideGamut3
+            | Self::ArriWideGamut4
+            | Self::VGamut
+            | Self::CinemaGamut => UNSPECIFIED,
+        }
+    }
+}
+
+impl ColorTransfer {
+    /// Returns the transfer function for the given H.273 `TransferCharacteristics` code point
+    ///
+    /// Unspecified (2) is treated as BT.709.
+    pub fn from_cicp(code_point: u8) -> Result<Self, CicpError> {
+        match code_point {
+            1 | UNSPECIFIED | 6 | 14 | 15 => Ok(Self::SDR),
+            4 => Ok(Self::Gamma22),
+            5 => Ok(Self::Gamma28),
+            8 => Ok(Self::Linear),
+            13 => Ok(Self::SRGB),
+            16 => Ok(Self::BT2100PQ),
+            18 => Ok(Self::BT2100HLG),
+            _ => Err(CicpError::UnsupportedTransferCharacteristics(code_point)),
+        }
+    }
+
+    /// Returns the H.273 `TransferCharacteristics` code point, or unspecified (2) if there is none
+    pub fn to_cicp(self) -> u8 {
+        match self {
+            Self::Linear => 8,
+            Self::Gamma22 => 4,
+            Self::Gamma28 => 5,
+            Self::SRGB => 13,
+            Self::SDR => 1,
+            Self::BT2100PQ => 16,
+            Self::BT2100HLG => 18,
+            Self::SLog3
+            | Self::ArriLogC3
+            | Self::ArriLogC4
+            | Self::VLog
+            | Self::CanonLog3
+            | Self::NLog => UNSPECIFIED,
+        }
+    }
+}
+
+impl ColorSpace {
+    /// Returns the color space for the given H.273 `MatrixCoefficients` code point
+    ///
+    /// Unspecified (2) is treated as BT.709. ICtCp (14) requires the `TransferCharacteristics` code point to be
+    /// either PQ (16) or HLG (18) to be resolved. Identity (0) is not a YUV color space and must be handled by the
+    /// caller.
+    pub fn from_cicp(code_point: u8, transfer_code_point: u8) -> Result<Self, CicpError> {
+        match code_point {
+            1 | UNSPECIFIED => Ok(Self::BT709),
+            4 => Ok(Self::Custom(LumaCoefficients::FCC)),
+            5 | 6 => Ok(Self::BT601),
+            7 => Ok(Self::SMPTE240M),
+            8 => Ok(Self::YCgCo),
+            9 => Ok(Self::BT2020),
+            10 => Ok(Self::BT2020CL),
+            14 => match ColorTransfer::from_cicp(transfer_code_point) {
+                Ok(ColorTransfer::BT2100PQ) => Ok(Self::ICtCpPQ),
+                Ok(ColorTransfer::BT2100HLG) => Ok(Self::ICtCpHLG),
+                _ => Err(CicpError::InvalidICtCpTransfer(transfer_code_point)),
+            },
+            _ => Err(CicpError::UnsupportedMatrixCoefficients(code_point)),
+        }
+    }
+
+    /// Returns the H.273 `MatrixCoefficients` code point, or unspecified (2) for custom coefficients other than FCC
+    ///
+    /// YCoCg-R is signaled as YCgCo (8) with a chroma bit depth one higher than luma, so it maps to the same
+    /// code point as [`ColorSpace::YCgCo`].
+    pub fn to_cicp(self) -> u8 {
+        match self {
+            Self::BT601 => 6,
+            Self::BT709 => 1,
+            Self::SMPTE240M => 7,
+            Self::YCgCo | Self::YCoCgR => 8,
+            Self::BT2020 => 9,
+            Self::BT2020CL => 10,
+            Self::Custom(coefficients) if coefficients == LumaCoefficients::FCC => 4,
+            Self::Custom(_) => UNSPECIFIED,
+            Self::ICtCpPQ | Self::ICtCpHLG => 14,
+        }
+    }
+}
+
+impl TryFrom<Cicp> for ColorInfo {
+    type Error = CicpError;
+
+    fn try_from(cicp: Cicp) -> Result<Self, Self::Error> {
+        let primaries = ColorPrimaries::from_cicp(cicp.color_primaries)?;
+        let transfer = ColorTransfer::from_cicp(cicp.transfer_characteristics)?;
+
+        // Identity matrix, the image is RGB (or GBR in the codec's plane order)
+        if cicp.matrix_coefficients == 0 {
+            return Ok(ColorInfo::RGB(RgbColorInfo {
+                transfer,
+                primaries,
+                full_range: cicp.full_range,
+                premultiplied_alpha: false,
+            }));
+        }
+
+        let space = ColorSpace::from_cicp(cicp.matrix_coefficients, cicp.transfer_characteristics)?;
+
+        Ok(ColorInfo::YUV(YuvColorInfo {
+            transfer,
+            primaries,
+            space,
+            full_range: cicp.full_range,
+        }))
+    }
+}
+
+impl TryFrom<(u8, u8, u8, bool)> for ColorInfo {
+    type Error = CicpError;
+
+    fn try_from(cicp: (u8, u8, u8, bool)) -> Result<Self, Self::Error> {
+        Self::try_from(Cicp::from(cicp))
+    }
+}
+
+impl From<ColorInfo> for Cicp {
+    fn from(color: ColorInfo) -> Self {
+        match color {
+            ColorInfo::RGB(rgb) => Cicp {
+                color_primaries: rgb.primaries.to_cicp(),
+                transfer_characteristics: rgb.transfer.to_cicp(),
+                matrix_coefficients: 0,
+                full_range: rgb.full_range,
+            },
+            ColorInfo::YUV(yuv) => Cicp {
+                color_primaries: yuv.primaries.to_cicp(),
+                transfer_characteristics: yuv.transfer.to_cicp(),
+                matrix_coefficients: yuv.space.to_cicp(),
+                full_range: yuv.full_range,
+            },
+        }
+    }
+}
+
+impl From<ColorInfo> for (u8, u8, u8, bool) {
+    fn from(color: ColorInfo) -> Self {
+        Cicp::from(color).into()
+    }
+}
+
+impl From<(u8, u8, u8, bool)> for Cicp {
+    fn from(value: (u8, u8, u8, bool)) -> Self {
+        let (color_primaries, transfer_characteristics, matrix_coefficients, full_range) = value;
+
+        Self {
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            full_range,
+        }
+    }
+}
+
+impl From<Cicp> for (u8, u8, u8, bool) {
+    fn from(cicp: Cicp) -> Self {
+        (
+            cicp.color_primaries,
+            cicp.transfer_characteristics,
+            cicp.matrix_coefficients,
+            cicp.full_range,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let cases = [
+            (1, 1, 1, false),
+            (9, 16, 9, false),
+            (9, 18, 14, true),
+            (9, 16, 14, false),
+            (7, 1, 6, false),
+            (1, 13, 0, true),
+            (1, 1, 0, false),
+            (1, 13, 8, true),
+            (9, 1, 10, false),
+            (7, 1, 7, false),
+            (1, 1, 4, false),
+        ];
+
+        for cicp in cases {
+            let color = ColorInfo::try_from(cicp).unwrap();
+
+            assert_eq!(<(u8, u8, u8, bool)>::from(color), cicp);
+        }
+    }
+
+    #[test]
+    fn unspecified() {
+        let color = ColorInfo::try_from((2, 2, 2, false)).unwrap();
+
+        assert_eq!(
+            color,
+            ColorInfo::YUV(YuvColorInfo {
+                transfer: ColorTransfer::SDR,
+                primaries: ColorPrimaries::BT709,
+                space: ColorSpace::BT709,
+                full_range: false,
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            ColorInfo::try_from((22, 1, 1, false)),
+            Err(CicpError::UnsupportedColorPrimaries(22))
+        );
+        assert_eq!(
+            ColorInfo::try_from((1, 17, 1, false)),
+            Err(CicpError::UnsupportedTransferCharacteristics(17))
+        );
+        assert_eq!(
+            ColorInfo::try_from((1, 1, 3, false)),
+            Err(CicpError::UnsupportedMatrixCoefficients(3))
+        );
+        assert_eq!(
+            ColorInfo::try_from((9, 1, 14, false)),
+            Err(CicpError::InvalidICtCpTransfer(1))
+        );
+        assert_eq!(
+            ColorInfo::try_from((9, 6, 14, false)),
+            Err(CicpError::InvalidICtCpTransfer(6))
+        );
+        assert_eq!(
+            ColorSpace::from_cicp(14, 15),
+            Err(CicpError::InvalidICtCpTransfer(15))
+        );
+    }
+}
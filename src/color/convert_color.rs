@@ -0,0 +1,299 @@
+use super::ColorInfo;
+use crate::formats::rgb::{RgbRange, RgbaPixel, RgbaSrc};
+use crate::formats::yuv_range::YuvRange;
+use crate::formats::{
+    Solid, TransferAndPrimariesConvert, max_value_for_bits, need_transfer_and_primaries_convert,
+};
+use crate::{ConvertError, ConvertOptions};
+
+/// Convert a single color value from `src_color` with `src_bits` bits per component to `dst_color` with `dst_bits`
+/// bits per component.
+///
+/// Values are the code values of the components in the order Y, U, V or R, G, B, e.g. `[940.0, 512.0, 512.0]` for
+/// white in 10-bit limited range YUV. The result is not rounded or clamped, divide it by `2^dst_bits - 1` to get
+/// normalized values of a full range color. Values outside of the nominal range are kept when converting between
+/// transfer functions or primaries, like [`ConvertOptions::preserve_out_of_range`] does.
+///
+/// Uses the same math as [`convert`](crate::convert).
+///
+/// ```
+/// # use ezk_image::*;
+/// let yuv = ColorInfo::YUV(YuvColorInfo {
+///     transfer: ColorTransfer::SDR,
+///     primaries: ColorPrimaries::BT709,
+///     space: ColorSpace::BT709,
+///     full_range: false,
+/// });
+///
+/// let rgb = ColorInfo::RGB(RgbColorInfo {
+///     transfer: ColorTransfer::SDR,
+///     primaries: ColorPrimaries::BT709,
+///     full_range: true,
+///     premultiplied_alpha: false,
+/// });
+///
+/// let [r, g, b] = convert_color(&yuv, 10, &rgb, 8, [940.0, 512.0, 512.0]).unwrap();
+/// assert!((r - 255.0).abs() < 0.01 && (g - 255.0).abs() < 0.01 && (b - 255.0).abs() < 0.01);
+/// ```
+///
+/// A bit depth of `1` has a maximum code value of `1.0`, so it can be used to pass or get normalized values
+/// (`0.0..=1.0` for full range colors), e.g. for floating point RGB:
+///
+/// ```
+/// # use ezk_image::*;
+/// # let yuv = ColorInfo::YUV(YuvColorInfo {
+/// #     transfer: ColorTransfer::SDR,
+/// #     primaries: ColorPrimaries::BT709,
+/// #     space: ColorSpace::BT709,
+/// #     full_range: false,
+/// # });
+/// let rgb = ColorInfo::RGB(RgbColorInfo::new(ColorTransfer::SRGB, ColorPrimaries::BT709));
+///
+/// let [r, g, b] = convert_color(&yuv, 8, &rgb, 1, [235.0, 128.0, 128.0]).unwrap();
+/// assert!((r - 1.0).abs() < 0.01 && (g - 1.0).abs() < 0.01 && (b - 1.0).abs() < 0.01);
+///
+/// let [y, u, v] = convert_color(&rgb, 1, &yuv, 8, [0.0, 0.0, 0.0]).unwrap();
+/// assert!((y - 16.0).abs() < 0.01 && (u - 128.0).abs() < 0.01 && (v - 128.0).abs() < 0.01);
+/// ```
+pub fn convert_color(
+    src_color: &ColorInfo,
+    src_bits: usize,
+    dst_color: &ColorInfo,
+    dst_bits: usize,
+    value: [f32; 3],
+) -> Result<[f32; 3], ConvertError> {
+    if !(1..=16).contains(&src_bits) || !(1..=16).contains(&dst_bits) {
+        return Err(ConvertError::InvalidColorInfo);
+    }
+
+    // Safety: f32 is not a SIMD type
+    unsafe {
+        let [r, g, b] = decode(src_color, src_bits, value)?;
+
+        let mut solid = Solid::new([r, g, b, 1.0]);
+
+        let px = if need_transfer_and_primaries_convert(src_color, dst_color) {
+            TransferAndPrimariesConvert::new(
+                src_color,
+                dst_color,
+                &ConvertOptions {
+                    preserve_out_of_range: true,
+                    ..Default::default()
+                },
+                solid,
+            )
+            .read::<f32>(0, 0)
+            .px00
+        } else {
+            solid.read::<f32>(0, 0).px00
+        };
+
+        encode(dst_color, dst_bits, [px.r, px.g, px.b])
+    }
+}
+
+/// Code values to normalized RGB
+unsafe fn decode(
+    color: &ColorInfo,
+    bits: usize,
+    value: [f32; 3],
+) -> Result<[f32; 3], ConvertError> {
+    let [c0, c1, c2] = value;
+
+    match color {
+        ColorInfo::RGB(..) => {
+            let px = RgbRange::new(color, bits).decode_rgb(RgbaPixel {
+                r: c0,
+                g: c1,
+                b: c2,
+                a: 1.0,
+            });
+
+            Ok([px.r, px.g, px.b])
+        }
+        ColorInfo::YUV(yuv) => {
+            yuv.space.validate(bits, yuv.full_range)?;
+
+            let max_value = max_value_for_bits(bits);
+            let range = YuvRange::new(bits, yuv.full_range);
+
+            let (r, g, b) = yuv.space.yuv_to_rgb(
+                &yuv.space.yuv_to_rgb_mat(),
+                yuv.transfer,
+                yuv.primaries.xyz_to_rgb_mat(),
+                max_value,
+                range.decode_y(c0 / max_value),
+                range.decode_uv(c1 / max_value),
+                range.decode_uv(c2 / max_value),
+            );
+
+            Ok([r, g, b])
+        }
+    }
+}
+
+/// Normalized RGB to code values
+pub(crate) unsafe fn encode(
+    color: &ColorInfo,
+    bits: usize,
+    rgb: [f32; 3],
+) -> Result<[f32; 3], ConvertError> {
+    let [r, g, b] = rgb;
+
+    match color {
+        ColorInfo::RGB(..) => {
+            let px = RgbRange::new(color, bits).encode_rgba(RgbaPixel { r, g, b, a: 1.0 });
+
+            Ok([px.r, px.g, px.b])
+        }
+        ColorInfo::YUV(yuv) => {
+            yuv.space.validate(bits, yuv.full_range)?;
+
+            let max_value = max_value_for_bits(bits);
+            let range = YuvRange::new(bits, yuv.full_range);
+
+            let (y, u, v) = yuv.space.rgb_to_yuv(
+                &yuv.space.rgb_to_yuv_mat(),
+                yuv.transfer,
+                yuv.primaries.rgb_to_xyz_mat(),
+                max_value,
+                r,
+                g,
+                b,
+            );
+
+            Ok([
+                range.encode_y(y) * max_value,
+                range.encode_uv(u) * max_value,
+                range.encode_uv(v) * max_value,
+            ])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_color;
+    use crate::{
+        ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, Image, ImageRef, PixelFormat,
+        RgbColorInfo, YuvColorInfo, convert,
+    };
+
+    fn yuv(space: ColorSpace, transfer: ColorTransfer, full_range: bool) -> ColorInfo {
+        ColorInfo::YUV(YuvColorInfo {
+            transfer,
+            primaries: ColorPrimaries::BT709,
+            space,
+            full_range,
+        })
+    }
+
+    fn srgb() -> ColorInfo {
+        ColorInfo::RGB(RgbColorInfo {
+            transfer: ColorTransfer::SRGB,
+            primaries: ColorPrimaries::BT709,
+            full_range: true,
+            premultiplied_alpha: false,
+        })
+    }
+
+    #[test]
+    fn matches_image_conversion() {
+        let src_color = yuv(ColorSpace::BT709, ColorTransfer::SDR, false);
+        let values = [
+            [64u16, 512, 512],
+            [940, 512, 512],
+            [300, 400, 700],
+            [700, 900, 100],
+        ];
+
+        for [y, u, v] in values {
+            let mut data = vec![];
+            for c in [y, u, v] {
+                data.extend(c.to_ne_bytes().repeat(4));
+            }
+
+            let src = Image::from_buffer(PixelFormat::I410, data, None, 2, 2, src_color).unwrap();
+            let mut dst = Image::blank(PixelFormat::RGB, 2, 2, srgb());
+            convert(&src, &mut dst).unwrap();
+
+            let expected = &dst.planes().next().unwrap().0[..3];
+
+            let rgb = convert_color(&src_color, 10, &srgb(), 8, [y, u, v].map(f32::from)).unwrap();
+            let rgb = rgb.map(|c| c.clamp(0.0, 255.0).round() as u8);
+
+            assert_eq!(rgb, expected, "{y} {u} {v}");
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        for space in [ColorSpace::BT601, ColorSpace::BT2020, ColorSpace::ICtCpPQ] {
+            let transfer = if space == ColorSpace::ICtCpPQ {
+                ColorTransfer::BT2100PQ
+            } else {
+                ColorTransfer::SDR
+            };
+
+            let color = yuv(space, transfer, true);
+
+            let value = [0.2, 0.7, 0.4];
+            let code_values = convert_color(&srgb(), 1, &color, 12, value).unwrap();
+            let rgb = convert_color(&color, 12, &srgb(), 1, code_values).unwrap();
+
+            for (a, b) in rgb.iter().zip(value) {
+                assert!((a - b).abs() < 1e-3, "{space:?} {rgb:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_out_of_range_values() {
+        let linear = ColorInfo::RGB(RgbColorInfo {
+            transfer: ColorTransfer::Linear,
+            primaries: ColorPrimaries::BT709,
+            full_range: true,
+            premultiplied_alpha: false,
+        });
+
+        let [r, g, b] = convert_color(&srgb(), 1, &linear, 1, [-0.5, 0.5, 1.5]).unwrap();
+
+        assert!((r + 0.214).abs() < 1e-3, "{r}");
+        assert!((g - 0.214).abs() < 1e-3, "{g}");
+        assert!(b > 1.0, "{b}");
+    }
+
+    #[test]
+    fn bt2020_reference_code_values() {
+        let rgb = ColorInfo::RGB(RgbColorInfo {
+            transfer: ColorTransfer::SDR,
+            primaries: ColorPrimaries::BT709,
+            full_range: true,
+            premultiplied_alpha: false,
+        });
+        let color = yuv(ColorSpace::BT2020, ColorTransfer::SDR, false);
+
+        // 10-bit limited range code values computed from Kr = 0.2627, Kb = 0.0593 (ITU-R BT.2020 table 4)
+        let values = [
+            ([0.0, 0.0, 0.0], [64.0, 512.0, 512.0]),
+            ([1.0, 1.0, 1.0], [940.0, 512.0, 512.0]),
+            ([1.0, 0.0, 0.0], [294.0, 387.0, 960.0]),
+            ([0.0, 1.0, 0.0], [658.0, 189.0, 100.0]),
+            ([0.0, 0.0, 1.0], [116.0, 960.0, 476.0]),
+        ];
+
+        for (value, expected) in values {
+            let code_values = convert_color(&rgb, 1, &color, 10, value)
+                .unwrap()
+                .map(f32::round);
+
+            assert_eq!(code_values, expected, "{value:?}");
+
+            let back = convert_color(&color, 10, &rgb, 1, code_values).unwrap();
+
+            for (a, b) in back.iter().zip(value) {
+                assert!((a - b).abs() < 2e-3, "{value:?} {back:?}");
+            }
+        }
+    }
+}
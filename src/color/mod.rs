@@ -1,15 +1,45 @@
+mod cicp;
+pub(crate) mod convert_color;
 pub(crate) mod primaries;
 pub(crate) mod space;
 pub(crate) mod transfer;
 
+pub use cicp::{Cicp, CicpError};
+pub use convert_color::convert_color;
 pub use primaries::ColorPrimaries;
-pub use space::ColorSpace;
+pub use space::{ColorSpace, LumaCoefficients};
 pub use transfer::ColorTransfer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RgbColorInfo {
     pub transfer: ColorTransfer,
     pub primaries: ColorPrimaries,
+    /// If the image uses either full or limited (studio swing) range
+    ///
+    /// - full range (0 - 255)
+    /// - limited range (16 - 235)
+    ///
+    /// Higher bit depths scale these values by `2^(N-8)`, e.g. 10-bit limited range is (64 - 940).
+    /// Alpha is always full range.
+    pub full_range: bool,
+    /// If the color components of formats with an alpha channel are premultiplied with alpha
+    ///
+    /// Conversions and the resizer premultiply or unpremultiply as needed. Ignored for formats without alpha.
+    pub premultiplied_alpha: bool,
+}
+
+impl RgbColorInfo {
+    /// Full range RGB with straight (not premultiplied) alpha
+    ///
+    /// Prefer this over a struct literal, so code keeps compiling when new fields are added.
+    pub const fn new(transfer: ColorTransfer, primaries: ColorPrimaries) -> Self {
+        Self {
+            transfer,
+            primaries,
+            full_range: true,
+            premultiplied_alpha: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,6 +51,8 @@ pub struct YuvColorInfo {
     ///
     /// - full range (0 - 255)
     /// - standard range Y (16 - 235), U & V (16 - 240)
+    ///
+    /// Higher bit depths scale these values by `2^(N-8)`, e.g. 10-bit standard range is Y (64 - 940), U & V (64 - 960).
     pub full_range: bool,
 }
 
@@ -45,6 +77,13 @@ impl ColorInfo {
             ColorInfo::YUV(yuv) => yuv.transfer,
         }
     }
+
+    pub(crate) fn premultiplied_alpha(&self) -> bool {
+        match self {
+            ColorInfo::RGB(rgb) => rgb.premultiplied_alpha,
+            ColorInfo::YUV(..) => false,
+        }
+    }
 }
 
 impl From<RgbColorInfo> for ColorInfo {
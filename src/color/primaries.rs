@@ -9,6 +9,18 @@ pub enum ColorPrimaries {
     BT709,
     /// ITU-R BT.2020
     BT2020,
+    /// Sony S-Gamut3
+    SGamut3,
+    /// Sony S-Gamut3.Cine
+    SGamut3Cine,
+    /// ARRI Wide Gamut 3 (used with LogC3)
+    ArriWideGamut3,
+    /// ARRI Wide Gamut 4 (used with LogC4)
+    ArriWideGamut4,
+    /// Panasonic V-Gamut
+    VGamut,
+    /// Canon Cinema Gamut
+    CinemaGamut,
 }
 
 impl ColorPrimaries {
@@ -20,6 +32,12 @@ impl ColorPrimaries {
             ST240 => &generated_consts::ST240_RGB_TO_XYZ,
             BT709 => &generated_consts::BT709_RGB_TO_XYZ,
             BT2020 => &generated_consts::BT2020_RGB_TO_XYZ,
+            SGamut3 => &generated_consts::SGAMUT3_RGB_TO_XYZ,
+            SGamut3Cine => &generated_consts::SGAMUT3CINE_RGB_TO_XYZ,
+            ArriWideGamut3 => &generated_consts::ARRIWIDEGAMUT3_RGB_TO_XYZ,
+            ArriWideGamut4 => &generated_consts::ARRIWIDEGAMUT4_RGB_TO_XYZ,
+            VGamut => &generated_consts::VGAMUT_RGB_TO_XYZ,
+            CinemaGamut => &generated_consts::CINEMAGAMUT_RGB_TO_XYZ,
         }
     }
 
@@ -31,6 +49,12 @@ impl ColorPrimaries {
             ST240 => &generated_consts::ST240_XYZ_TO_RGB,
             BT709 => &generated_consts::BT709_XYZ_TO_RGB,
             BT2020 => &generated_consts::BT2020_XYZ_TO_RGB,
+            SGamut3 => &generated_consts::SGAMUT3_XYZ_TO_RGB,
+            SGamut3Cine => &generated_consts::SGAMUT3CINE_XYZ_TO_RGB,
+            ArriWideGamut3 => &generated_consts::ARRIWIDEGAMUT3_XYZ_TO_RGB,
+            ArriWideGamut4 => &generated_consts::ARRIWIDEGAMUT4_XYZ_TO_RGB,
+            VGamut => &generated_consts::VGAMUT_XYZ_TO_RGB,
+            CinemaGamut => &generated_consts::CINEMAGAMUT_XYZ_TO_RGB,
         }
     }
 }
@@ -104,6 +128,72 @@ mod generated_consts {
         [-0.3556708, 1.6164812, -0.04277061],
         [-0.25336626, 0.01576853, 0.9421032],
     ];
+
+    pub(super) const SGAMUT3_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.7064828, 0.2709797, -0.0096778665],
+        [0.12880103, 0.7866063, 0.004600032],
+        [0.11517217, -0.057586085, 1.0941356],
+    ];
+    pub(super) const SGAMUT3_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.5073998, -0.5181518, 0.015511724],
+        [-0.24582212, 1.3553915, -0.007872771],
+        [-0.17161168, 0.12587869, 0.91191643],
+    ];
+
+    pub(super) const SGAMUT3CINE_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.59908396, 0.21507585, -0.03206584],
+        [0.24892549, 0.8850684, -0.027658427],
+        [0.10244648, -0.10014431, 1.1487819],
+    ];
+    pub(super) const SGAMUT3CINE_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.8467789, -0.44415337, 0.04085539],
+        [-0.52598614, 1.259443, 0.01564094],
+        [-0.2105452, 0.1494, 0.86820734],
+    ];
+
+    pub(super) const ARRIWIDEGAMUT3_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.6380076, 0.29195377, 0.0027982707],
+        [0.21470383, 0.8238409, -0.06703423],
+        [0.09774444, -0.115794815, 1.1532937],
+    ];
+    pub(super) const ARRIWIDEGAMUT3_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.7890656, -0.6398487, -0.041531533],
+        [-0.48253387, 1.3964001, 0.08233537],
+        [-0.20007576, 0.19443229, 0.87886846],
+    ];
+
+    pub(super) const ARRIWIDEGAMUT4_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.7048584, 0.2545242, -2.859183e-8],
+        [0.1297603, 0.78147775, 0.0],
+        [0.1158373, -0.036001902, 1.0890576],
+    ];
+    pub(super) const ARRIWIDEGAMUT4_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.5092151, -0.49154538, 3.962253e-8],
+        [-0.25059727, 1.3612454, -6.5791146e-9],
+        [-0.16881144, 0.097282924, 0.9182251],
+    ];
+
+    pub(super) const VGAMUT_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.67964447, 0.26068553, -0.009310217],
+        [0.15221141, 0.7748944, -0.0046124626],
+        [0.11860005, -0.035580013, 1.1029804],
+    ];
+    pub(super) const VGAMUT_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.5890115, -0.5340528, 0.011179476],
+        [-0.31320444, 1.3960112, 0.003194117],
+        [-0.18096484, 0.102457665, 0.9055352],
+    ];
+
+    pub(super) const CINEMAGAMUT_RGB_TO_XYZ: [[f32; 3]; 3] = [
+        [0.7160496, 0.26126134, -0.009676366],
+        [0.12968348, 0.8696422, -0.23648165],
+        [0.104722805, -0.13090351, 1.3352157],
+    ];
+    pub(super) const CINEMAGAMUT_XYZ_TO_RGB: [[f32; 3]; 3] = [
+        [1.4898185, -0.45816657, -0.070349656],
+        [-0.26089594, 1.2616278, 0.22155768],
+        [-0.14242654, 0.15962365, 0.77618164],
+    ];
 }
 
 #[cfg(test)]
@@ -135,6 +225,42 @@ mod generate_matrices {
                 xy(0.131, 0.046),
                 xy(0.3127, 0.3290),
             ],
+            SGamut3 => [
+                xy(0.730, 0.280),
+                xy(0.140, 0.855),
+                xy(0.100, -0.050),
+                xy(0.3127, 0.3290),
+            ],
+            SGamut3Cine => [
+                xy(0.766, 0.275),
+                xy(0.225, 0.800),
+                xy(0.089, -0.087),
+                xy(0.3127, 0.3290),
+            ],
+            ArriWideGamut3 => [
+                xy(0.6840, 0.3130),
+                xy(0.2210, 0.8480),
+                xy(0.0861, -0.1020),
+                xy(0.3127, 0.3290),
+            ],
+            ArriWideGamut4 => [
+                xy(0.7347, 0.2653),
+                xy(0.1424, 0.8576),
+                xy(0.0991, -0.0308),
+                xy(0.3127, 0.3290),
+            ],
+            VGamut => [
+                xy(0.730, 0.280),
+                xy(0.165, 0.840),
+                xy(0.100, -0.030),
+                xy(0.3127, 0.3290),
+            ],
+            CinemaGamut => [
+                xy(0.740, 0.270),
+                xy(0.170, 1.140),
+                xy(0.080, -0.100),
+                xy(0.3127, 0.3290),
+            ],
         }
     }
 
@@ -168,14 +294,26 @@ mod generate_matrices {
     #[test]
     #[ignore]
     fn run() {
-        let primaries = [ST240, BT709, BT2020];
+        let primaries = [
+            ST240,
+            BT709,
+            BT2020,
+            SGamut3,
+            SGamut3Cine,
+            ArriWideGamut3,
+            ArriWideGamut4,
+            VGamut,
+            CinemaGamut,
+        ];
 
         for primaries in primaries {
             let rgb_to_xyz = rgb_to_xyz_mat(primaries);
             let xyz_to_rgb = rgb_to_xyz.try_inverse().unwrap();
 
-            println!("pub(super) const {primaries:?}_RGB_TO_XYZ: [[f32; 3]; 3] = {rgb_to_xyz:?};");
-            println!("pub(super) const {primaries:?}_XYZ_TO_RGB: [[f32; 3]; 3] = {xyz_to_rgb:?};");
+            let name = format!("{primaries:?}").to_uppercase();
+
+            println!("pub(super) const {name}_RGB_TO_XYZ: [[f32; 3]; 3] = {rgb_to_xyz:?};");
+            println!("pub(super) const {name}_XYZ_TO_RGB: [[f32; 3]; 3] = {xyz_to_rgb:?};");
             println!()
         }
     }
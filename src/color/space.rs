@@ -1,8 +1,8 @@
 #![allow(clippy::too_many_arguments)]
 
-use crate::ColorTransfer;
 use crate::color::mat_idxs::*;
 use crate::vector::Vector;
+use crate::{ColorTransfer, ConvertError};
 
 /// Color space used for RGB to YUV conversion
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,36 +16,167 @@ pub enum ColorSpace {
     /// YUV Rec. ITU-R BT.2020-2
     BT2020,
 
+    /// YUV Rec. ITU-R BT.2020-2 constant luminance
+    ///
+    /// Luma is calculated from linear RGB and the chroma components are derived using the non-linear equations
+    /// of BT.2020 table 4, so this requires a valid transfer function.
+    BT2020CL,
+
+    /// YUV SMPTE ST 240M (1035i HDTV)
+    SMPTE240M,
+
     /// ICtCp Rec. ITU-R BT.2100-2 ICtCp (PQ transfer)
     ICtCpPQ,
 
     /// ICtCp Rec. ITU-R BT.2100-2 ICtCp (HLG transfer)
     ICtCpHLG,
+
+    /// YCgCo Rec. ITU-T H.273 (matrix coefficients 8)
+    ///
+    /// Cg is stored in the U plane, Co in the V plane.
+    YCgCo,
+
+    /// Reversible YCoCg-R used for lossless RGB coding
+    ///
+    /// Cg and Co require one more bit of precision than Y, so this is only supported for formats with 16 bit
+    /// primitives (e.g. 10-bit Y with 11-bit Cg/Co). The chroma planes store `Cg + 2^N` and `Co + 2^N` where N is the
+    /// format's bit depth. Requires full range. Conversion is bit exact for 4:4:4 formats.
+    YCoCgR,
+
+    /// YUV with custom luma coefficients
+    Custom(LumaCoefficients),
+}
+
+/// Luma coefficients Kr and Kb used to derive a YUV color space, Kg is `1 - Kr - Kb`
+#[derive(Debug, Clone, Copy)]
+pub struct LumaCoefficients {
+    /// Weight of red in luma
+    pub kr: f32,
+    /// Weight of blue in luma
+    pub kb: f32,
+}
+
+impl LumaCoefficients {
+    /// United States Federal Communications Commission (Title 47 Code of Federal Regulations 73.682 (a) (20))
+    pub const FCC: Self = Self { kr: 0.30, kb: 0.11 };
+
+    /// Create coefficients from the red and blue weights, the weight of green (Kg) is derived as `1 - Kr - Kb`
+    ///
+    /// Both must be positive and sum up to less than 1, otherwise conversions using them fail with
+    /// [`ConvertError::InvalidColorInfo`].
+    pub const fn new(kr: f32, kb: f32) -> Self {
+        Self { kr, kb }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.kr > 0.0 && self.kb > 0.0 && self.kr + self.kb < 1.0
+    }
+
+    fn yuv_to_rgb_matrix(&self) -> [[f32; 3]; 3] {
+        yuv_to_rgb_matrix(self.kr, 1.0 - self.kr - self.kb, self.kb)
+    }
+
+    fn rgb_to_yuv_matrix(&self) -> [[f32; 3]; 3] {
+        rgb_to_yuv_matrix(self.kr, 1.0 - self.kr - self.kb, self.kb)
+    }
+}
+
+// Compare the bit representation to be able to implement Eq
+impl PartialEq for LumaCoefficients {
+    fn eq(&self, other: &Self) -> bool {
+        self.kr.to_bits() == other.kr.to_bits() && self.kb.to_bits() == other.kb.to_bits()
+    }
 }
 
+impl Eq for LumaCoefficients {}
+
 impl ColorSpace {
+    /// Check if the color space can be used with a format of the given bit depth & range
+    pub(crate) fn validate(
+        &self,
+        bits_per_component: usize,
+        full_range: bool,
+    ) -> Result<(), ConvertError> {
+        let valid = match self {
+            // Cg & Co require one more bit than Y
+            ColorSpace::YCoCgR => bits_per_component > 8 && full_range,
+            ColorSpace::Custom(coefficients) => coefficients.is_valid(),
+            _ => true,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(ConvertError::InvalidColorInfo)
+        }
+    }
+
+    /// Matrix passed to [`ColorSpace::yuv_to_rgb`] & [`ColorSpace::yx4_uv_to_rgb`]
+    ///
+    /// Resolved once when creating a converter, so custom coefficients aren't turned into a matrix for every block.
+    /// Color spaces which aren't matrix based ignore it.
+    pub(crate) fn yuv_to_rgb_mat(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorSpace::BT601 => BT601_YUV_TO_RGB,
+            ColorSpace::BT709 => BT709_YUV_TO_RGB,
+            ColorSpace::BT2020 => BT2020_YUV_TO_RGB,
+            ColorSpace::SMPTE240M => SMPTE240M_YUV_TO_RGB,
+            ColorSpace::Custom(c) => c.yuv_to_rgb_matrix(),
+            ColorSpace::BT2020CL
+            | ColorSpace::ICtCpPQ
+            | ColorSpace::ICtCpHLG
+            | ColorSpace::YCgCo
+            | ColorSpace::YCoCgR => [[0.0; 3]; 3],
+        }
+    }
+
+    /// Matrix passed to [`ColorSpace::rgb_to_yuv`] & [`ColorSpace::rgbx4_to_yx4_uv`], see [`ColorSpace::yuv_to_rgb_mat`]
+    pub(crate) fn rgb_to_yuv_mat(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorSpace::BT601 => BT601_RGB_TO_YUV,
+            ColorSpace::BT709 => BT709_RGB_TO_YUV,
+            ColorSpace::BT2020 => BT2020_RGB_TO_YUV,
+            ColorSpace::SMPTE240M => SMPTE240M_RGB_TO_YUV,
+            ColorSpace::YCgCo => YCGCO_RGB_TO_YUV,
+            ColorSpace::Custom(c) => c.rgb_to_yuv_matrix(),
+            ColorSpace::BT2020CL
+            | ColorSpace::ICtCpPQ
+            | ColorSpace::ICtCpHLG
+            | ColorSpace::YCoCgR => [[0.0; 3]; 3],
+        }
+    }
+
     #[inline(always)]
     pub(crate) unsafe fn yuv_to_rgb<V: Vector>(
         &self,
+        yuv_to_rgb: &[[f32; 3]; 3],
         transfer: ColorTransfer,
         xyz_to_rgb: &'static [[f32; 3]; 3],
+        max_value: f32,
         y: V,
         u: V,
         v: V,
     ) -> (V, V, V) {
         match self {
-            ColorSpace::BT601 => convert_yuv_to_rgb_matrix(&BT601_YUV_TO_RGB, y, u, v),
-            ColorSpace::BT709 => convert_yuv_to_rgb_matrix(&BT709_YUV_TO_RGB, y, u, v),
-            ColorSpace::BT2020 => convert_yuv_to_rgb_matrix(&BT2020_YUV_TO_RGB, y, u, v),
+            ColorSpace::BT601
+            | ColorSpace::BT709
+            | ColorSpace::BT2020
+            | ColorSpace::SMPTE240M
+            | ColorSpace::Custom(_) => convert_yuv_to_rgb_matrix(yuv_to_rgb, y, u, v),
+            ColorSpace::BT2020CL => bt2020_cl::yuv_to_rgb(transfer, xyz_to_rgb, y, u, v),
             ColorSpace::ICtCpPQ => bt2100::pq_yuv_to_rgb(transfer, xyz_to_rgb, y, u, v),
             ColorSpace::ICtCpHLG => bt2100::hlg_yuv_to_rgb(transfer, xyz_to_rgb, y, u, v),
+            ColorSpace::YCgCo => ycgco::yuv_to_rgb(y, u, v),
+            ColorSpace::YCoCgR => ycocg_r::yuv_to_rgb(max_value, y, u, v),
         }
     }
     #[inline(always)]
     pub(crate) unsafe fn yx4_uv_to_rgb<V: Vector>(
         &self,
+        yuv_to_rgb: &[[f32; 3]; 3],
         transfer: ColorTransfer,
         xyz_to_rgb: &'static [[f32; 3]; 3],
+        max_value: f32,
         y00: V,
         y01: V,
         y10: V,
@@ -54,15 +185,24 @@ impl ColorSpace {
         v: V,
     ) -> [[V; 3]; 4] {
         match self {
-            ColorSpace::BT601 => {
-                convert_yx4_uv_to_rgb_matrix(&BT601_YUV_TO_RGB, y00, y01, y10, y11, u, v)
-            }
-            ColorSpace::BT709 => {
-                convert_yx4_uv_to_rgb_matrix(&BT709_YUV_TO_RGB, y00, y01, y10, y11, u, v)
-            }
-            ColorSpace::BT2020 => {
-                convert_yx4_uv_to_rgb_matrix(&BT2020_YUV_TO_RGB, y00, y01, y10, y11, u, v)
+            ColorSpace::BT601
+            | ColorSpace::BT709
+            | ColorSpace::BT2020
+            | ColorSpace::SMPTE240M
+            | ColorSpace::Custom(_) => {
+                convert_yx4_uv_to_rgb_matrix(yuv_to_rgb, y00, y01, y10, y11, u, v)
             }
+            ColorSpace::BT2020CL => bt2100_yx4_uv_to_rgb(
+                bt2020_cl::yuv_to_rgb,
+                transfer,
+                xyz_to_rgb,
+                y00,
+                y01,
+                y10,
+                y11,
+                u,
+                v,
+            ),
             ColorSpace::ICtCpPQ => bt2100_yx4_uv_to_rgb(
                 bt2100::pq_yuv_to_rgb,
                 transfer,
@@ -85,45 +225,63 @@ impl ColorSpace {
                 u,
                 v,
             ),
+            ColorSpace::YCgCo => ycgco::yx4_uv_to_rgb(y00, y01, y10, y11, u, v),
+            ColorSpace::YCoCgR => ycocg_r::yx4_uv_to_rgb(max_value, y00, y01, y10, y11, u, v),
         }
     }
 
     #[inline(always)]
     pub(crate) unsafe fn rgb_to_yuv<V: Vector>(
         &self,
+        rgb_to_yuv: &[[f32; 3]; 3],
         transfer: ColorTransfer,
         rgb_to_xyz: &'static [[f32; 3]; 3],
+        max_value: f32,
         r: V,
         g: V,
         b: V,
     ) -> (V, V, V) {
         match self {
-            ColorSpace::BT601 => convert_rgb_to_yuv_matrix(&BT601_RGB_TO_YUV, r, g, b),
-            ColorSpace::BT709 => convert_rgb_to_yuv_matrix(&BT709_RGB_TO_YUV, r, g, b),
-            ColorSpace::BT2020 => convert_rgb_to_yuv_matrix(&BT2020_RGB_TO_YUV, r, g, b),
+            ColorSpace::BT601
+            | ColorSpace::BT709
+            | ColorSpace::BT2020
+            | ColorSpace::SMPTE240M
+            | ColorSpace::YCgCo
+            | ColorSpace::Custom(_) => convert_rgb_to_yuv_matrix(rgb_to_yuv, r, g, b),
+            ColorSpace::BT2020CL => bt2020_cl::rgb_to_yuv(transfer, rgb_to_xyz, r, g, b),
             ColorSpace::ICtCpPQ => bt2100::pq_rgb_to_yuv(transfer, rgb_to_xyz, r, g, b),
             ColorSpace::ICtCpHLG => bt2100::hlg_rgb_to_yuv(transfer, rgb_to_xyz, r, g, b),
+            ColorSpace::YCoCgR => ycocg_r::rgb_to_yuv(max_value, r, g, b),
         }
     }
     #[inline(always)]
     pub(crate) unsafe fn rgbx4_to_yx4_uv<V: Vector>(
         &self,
+        rgb_to_yuv: &[[f32; 3]; 3],
         transfer: ColorTransfer,
         rgb_to_xyz: &'static [[f32; 3]; 3],
+        max_value: f32,
         r: [V; 4],
         g: [V; 4],
         b: [V; 4],
     ) -> ([V; 4], V, V) {
         match self {
-            ColorSpace::BT601 => rgbx4_to_yx4_uv_matrix(&BT601_RGB_TO_YUV, r, g, b),
-            ColorSpace::BT709 => rgbx4_to_yx4_uv_matrix(&BT709_RGB_TO_YUV, r, g, b),
-            ColorSpace::BT2020 => rgbx4_to_yx4_uv_matrix(&BT2020_RGB_TO_YUV, r, g, b),
+            ColorSpace::BT601
+            | ColorSpace::BT709
+            | ColorSpace::BT2020
+            | ColorSpace::SMPTE240M
+            | ColorSpace::YCgCo
+            | ColorSpace::Custom(_) => rgbx4_to_yx4_uv_matrix(rgb_to_yuv, r, g, b),
+            ColorSpace::BT2020CL => {
+                bt2100_rgbx4_to_yx4_uv(bt2020_cl::rgb_to_yuv, transfer, rgb_to_xyz, r, g, b)
+            }
             ColorSpace::ICtCpPQ => {
                 bt2100_rgbx4_to_yx4_uv(bt2100::pq_rgb_to_yuv, transfer, rgb_to_xyz, r, g, b)
             }
             ColorSpace::ICtCpHLG => {
                 bt2100_rgbx4_to_yx4_uv(bt2100::hlg_rgb_to_yuv, transfer, rgb_to_xyz, r, g, b)
             }
+            ColorSpace::YCoCgR => ycocg_r::rgbx4_to_yx4_uv(max_value, r, g, b),
         }
     }
 }
@@ -267,22 +425,30 @@ unsafe fn rgbx4_to_yx4_uv_matrix<V: Vector>(
 }
 
 #[rustfmt::skip]
+const fn yuv_to_rgb_matrix(kr: f32, kg: f32, kb: f32) -> [[f32; 3]; 3] {
+    [
+        // R               G                                B
+        [1.0,              1.0,                             1.0            ], // Y
+        [0.0,              (-(kb / kg)) * (2.0 - 2.0 * kb), 2.0 - 2.0 * kb], // U
+        [(2.0 - 2.0 * kr), (-(kr / kg)) * (2.0 - 2.0 * kr), 0.0            ], // V
+    ]
+}
+
+#[rustfmt::skip]
+const fn rgb_to_yuv_matrix(kr: f32, kg: f32, kb: f32) -> [[f32; 3]; 3] {
+    [
+        // Y                       U                         V
+        [kr,                       kg,                       kb                      ], // R
+        [-0.5 * (kr / (1.0 - kb)), -0.5 * (kg / (1.0 - kb)), 0.5                     ], // G
+        [0.5,                      -0.5 * (kg / (1.0 - kr)), -0.5 * (kb / (1.0 - kr))], // B
+    ]
+}
+
 macro_rules! make_matrices {
     ($($yuv_to_rgb:ident, $rgb_to_yuv:ident: $kr:expr, $kg:expr, $kb:expr;)*) => {
         $(
-        pub(crate) const $yuv_to_rgb: [[f32; 3]; 3] = [
-            // R                 G                                  B
-            [1.0,                1.0,                               1.0            ], // Y
-            [0.0,               (-($kb / $kg)) * (2.0 - 2.0 * $kb), 2.0 - 2.0 * $kb], // U
-            [(2.0 - 2.0 * $kr), (-($kr / $kg)) * (2.0 - 2.0 * $kr), 0.0            ], // V
-        ];
-
-        pub(crate) const $rgb_to_yuv: [[f32; 3]; 3] = [
-            // Y                         U                           V
-            [$kr,                        $kg,                        $kb                       ], // R
-            [-0.5 * ($kr / (1.0 - $kb)), -0.5 * ($kg / (1.0 - $kb)), 0.5                       ], // G
-            [0.5,                        -0.5 * ($kg / (1.0 - $kr)), -0.5 * ($kb / (1.0 - $kr))], // B
-        ];
+        pub(crate) const $yuv_to_rgb: [[f32; 3]; 3] = yuv_to_rgb_matrix($kr, $kg, $kb);
+        pub(crate) const $rgb_to_yuv: [[f32; 3]; 3] = rgb_to_yuv_matrix($kr, $kg, $kb);
         )*
     };
 }
@@ -290,7 +456,185 @@ macro_rules! make_matrices {
 make_matrices! {
     BT601_YUV_TO_RGB, BT601_RGB_TO_YUV: 0.299,  0.587,  0.114;
     BT709_YUV_TO_RGB, BT709_RGB_TO_YUV: 0.2126, 0.7152, 0.0722;
-    BT2020_YUV_TO_RGB, BT2020_RGB_TO_YUV: 0.2627, 0.322, 0.0593;
+    BT2020_YUV_TO_RGB, BT2020_RGB_TO_YUV: 0.2627, 0.6780, 0.0593;
+    SMPTE240M_YUV_TO_RGB, SMPTE240M_RGB_TO_YUV: 0.212, 0.701, 0.087;
+}
+
+#[rustfmt::skip]
+const YCGCO_RGB_TO_YUV: [[f32; 3]; 3] = [
+    // R      G     B
+    [ 0.25,  0.5,  0.25], // Y
+    [-0.25,  0.5, -0.25], // Cg
+    [ 0.5,   0.0, -0.5 ], // Co
+];
+
+mod ycgco {
+    use crate::vector::Vector;
+
+    #[inline(always)]
+    pub(super) unsafe fn yuv_to_rgb<V: Vector>(y: V, cg: V, co: V) -> (V, V, V) {
+        // t = Y - Cg
+        let t = y.vsub(cg);
+
+        let r = t.vadd(co);
+        let g = y.vadd(cg);
+        let b = t.vsub(co);
+
+        (r, g, b)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn yx4_uv_to_rgb<V: Vector>(
+        y00: V,
+        y01: V,
+        y10: V,
+        y11: V,
+        cg: V,
+        co: V,
+    ) -> [[V; 3]; 4] {
+        let (left_cg, right_cg) = cg.zip(cg);
+        let (left_co, right_co) = co.zip(co);
+
+        let (r00, g00, b00) = yuv_to_rgb(y00, left_cg, left_co);
+        let (r01, g01, b01) = yuv_to_rgb(y01, right_cg, right_co);
+        let (r10, g10, b10) = yuv_to_rgb(y10, left_cg, left_co);
+        let (r11, g11, b11) = yuv_to_rgb(y11, right_cg, right_co);
+
+        [
+            [r00, g00, b00],
+            [r01, g01, b01],
+            [r10, g10, b10],
+            [r11, g11, b11],
+        ]
+    }
+}
+
+/// YCoCg-R lifting as integer operations on f32
+///
+/// Inputs and outputs use the same normalized representation as every other color space, but Cg and Co are
+/// offset by `2^N` code values instead of `2^(N-1)`, since they use one bit more than Y.
+mod ycocg_r {
+    use crate::vector::Vector;
+
+    /// `x >> 1` for integer values stored in floats
+    #[inline(always)]
+    unsafe fn shr1<V: Vector>(x: V) -> V {
+        x.vmulf(0.5).vsubf(0.25).vround()
+    }
+
+    #[inline(always)]
+    unsafe fn to_code_value<V: Vector>(max_value: f32, x: V) -> V {
+        x.vmulf(max_value).vround().vmaxf(0.0).vminf(max_value)
+    }
+
+    #[inline(always)]
+    unsafe fn chroma_to_code_value<V: Vector>(max_value: f32, x: V) -> V {
+        // The range conversion already removed the 2^(N-1) achromatic offset, remove the remaining 2^(N-1)
+        x.vmulf(max_value).vround().vsubf((max_value + 1.0) * 0.5)
+    }
+
+    #[inline(always)]
+    unsafe fn chroma_from_code_value<V: Vector>(max_value: f32, x: V) -> V {
+        // Keep the stored value inside the N+1 bits Cg & Co may use (0..=2^(N+1)-1)
+        let half = (max_value + 1.0) * 0.5;
+
+        x.vmaxf(-(max_value + 1.0))
+            .vminf(max_value)
+            .vaddf(half)
+            .vdivf(max_value)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rgb_to_yuv<V: Vector>(max_value: f32, r: V, g: V, b: V) -> (V, V, V) {
+        let r = to_code_value(max_value, r);
+        let g = to_code_value(max_value, g);
+        let b = to_code_value(max_value, b);
+
+        // Co = R - B
+        let co = r.vsub(b);
+        // t = B + (Co >> 1)
+        let t = b.vadd(shr1(co));
+        // Cg = G - t
+        let cg = g.vsub(t);
+        // Y = t + (Cg >> 1)
+        let y = t.vadd(shr1(cg));
+
+        (
+            y.vdivf(max_value),
+            chroma_from_code_value(max_value, cg),
+            chroma_from_code_value(max_value, co),
+        )
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn yuv_to_rgb<V: Vector>(max_value: f32, y: V, cg: V, co: V) -> (V, V, V) {
+        let y = y.vmulf(max_value).vround();
+        let cg = chroma_to_code_value(max_value, cg);
+        let co = chroma_to_code_value(max_value, co);
+
+        // t = Y - (Cg >> 1)
+        let t = y.vsub(shr1(cg));
+        // G = Cg + t
+        let g = cg.vadd(t);
+        // B = t - (Co >> 1)
+        let b = t.vsub(shr1(co));
+        // R = B + Co
+        let r = b.vadd(co);
+
+        (r.vdivf(max_value), g.vdivf(max_value), b.vdivf(max_value))
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn yx4_uv_to_rgb<V: Vector>(
+        max_value: f32,
+        y00: V,
+        y01: V,
+        y10: V,
+        y11: V,
+        cg: V,
+        co: V,
+    ) -> [[V; 3]; 4] {
+        let (left_cg, right_cg) = cg.zip(cg);
+        let (left_co, right_co) = co.zip(co);
+
+        let (r00, g00, b00) = yuv_to_rgb(max_value, y00, left_cg, left_co);
+        let (r01, g01, b01) = yuv_to_rgb(max_value, y01, right_cg, right_co);
+        let (r10, g10, b10) = yuv_to_rgb(max_value, y10, left_cg, left_co);
+        let (r11, g11, b11) = yuv_to_rgb(max_value, y11, right_cg, right_co);
+
+        [
+            [r00, g00, b00],
+            [r01, g01, b01],
+            [r10, g10, b10],
+            [r11, g11, b11],
+        ]
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn rgbx4_to_yx4_uv<V: Vector>(
+        max_value: f32,
+        r: [V; 4],
+        g: [V; 4],
+        b: [V; 4],
+    ) -> ([V; 4], V, V) {
+        let (y00, _, _) = rgb_to_yuv(max_value, r[0], g[0], b[0]);
+        let (y01, _, _) = rgb_to_yuv(max_value, r[1], g[1], b[1]);
+        let (y10, _, _) = rgb_to_yuv(max_value, r[2], g[2], b[2]);
+        let (y11, _, _) = rgb_to_yuv(max_value, r[3], g[3], b[3]);
+
+        // Average the 2x2 rgb pixel blocks and calculate Cg & Co from it
+        let (rgb0_r, rgb1_r) = r[0].vadd(r[2]).unzip(r[1].vadd(r[3]));
+        let (rgb0_g, rgb1_g) = g[0].vadd(g[2]).unzip(g[1].vadd(g[3]));
+        let (rgb0_b, rgb1_b) = b[0].vadd(b[2]).unzip(b[1].vadd(b[3]));
+
+        let r = rgb0_r.vadd(rgb1_r).vmulf(0.25);
+        let g = rgb0_g.vadd(rgb1_g).vmulf(0.25);
+        let b = rgb0_b.vadd(rgb1_b).vmulf(0.25);
+
+        let (_, cg, co) = rgb_to_yuv(max_value, r, g, b);
+
+        ([y00, y01, y10, y11], cg, co)
+    }
 }
 
 type BT2100ConvertFn<V> = unsafe fn(ColorTransfer, &[[f32; 3]; 3], V, V, V) -> (V, V, V);
@@ -337,15 +681,84 @@ unsafe fn bt2100_rgbx4_to_yx4_uv<V: Vector>(
     let yuv10 = f(transfer, rgb_to_xyz, r[2], g[2], b[2]);
     let yuv11 = f(transfer, rgb_to_xyz, r[3], g[3], b[3]);
 
-    let u = yuv00.1.vadd(yuv01.1).vadd(yuv00.1).vadd(yuv00.1);
-    let v = yuv00.2.vadd(yuv01.2).vadd(yuv00.2).vadd(yuv00.2);
+    // Average the U/V values of the 2x2 pixel blocks
+    let (u0, u1) = yuv00.1.vadd(yuv10.1).unzip(yuv01.1.vadd(yuv11.1));
+    let (v0, v1) = yuv00.2.vadd(yuv10.2).unzip(yuv01.2.vadd(yuv11.2));
 
-    let u = u.vdivf(4.0);
-    let v = v.vdivf(4.0);
+    let u = u0.vadd(u1).vmulf(0.25);
+    let v = v0.vadd(v1).vmulf(0.25);
 
     ([yuv00.0, yuv01.0, yuv10.0, yuv11.0], u, v)
 }
 
+/// Rec. ITU-R BT.2020-2 constant luminance, table 4
+mod bt2020_cl {
+    use crate::ColorTransfer;
+    use crate::vector::Vector;
+
+    const KR: f32 = 0.2627;
+    const KG: f32 = 0.6780;
+    const KB: f32 = 0.0593;
+
+    // Chroma divisors for negative (N) and positive (P) color differences
+    const NB: f32 = 1.9404;
+    const PB: f32 = 1.5816;
+    const NR: f32 = 1.7184;
+    const PR: f32 = 0.9936;
+
+    #[inline(always)]
+    pub(super) unsafe fn rgb_to_yuv<V: Vector>(
+        transfer: ColorTransfer,
+        _rgb_to_xyz: &[[f32; 3]; 3],
+        r: V,
+        g: V,
+        b: V,
+    ) -> (V, V, V) {
+        let (mut r_linear, mut g_linear, mut b_linear) = (r, g, b);
+        transfer.scaled_to_linear_v(&mut [&mut r_linear, &mut g_linear, &mut b_linear]);
+
+        // Yc' = EOTF^-1(0.2627R + 0.6780G + 0.0593B)
+        let mut y = r_linear
+            .vmulf(KR)
+            .vadd(g_linear.vmulf(KG))
+            .vadd(b_linear.vmulf(KB));
+        transfer.linear_to_scaled_v(&mut [&mut y]);
+
+        let b_diff = b.vsub(y);
+        let r_diff = r.vsub(y);
+
+        let u = V::select(b_diff.vdivf(NB), b_diff.vdivf(PB), b_diff.lef(0.0));
+        let v = V::select(r_diff.vdivf(NR), r_diff.vdivf(PR), r_diff.lef(0.0));
+
+        (y, u, v)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn yuv_to_rgb<V: Vector>(
+        transfer: ColorTransfer,
+        _xyz_to_rgb: &[[f32; 3]; 3],
+        y: V,
+        u: V,
+        v: V,
+    ) -> (V, V, V) {
+        let b = y.vadd(V::select(u.vmulf(NB), u.vmulf(PB), u.lef(0.0)));
+        let r = y.vadd(V::select(v.vmulf(NR), v.vmulf(PR), v.lef(0.0)));
+
+        let (mut y_linear, mut r_linear, mut b_linear) = (y, r, b);
+        transfer.scaled_to_linear_v(&mut [&mut y_linear, &mut r_linear, &mut b_linear]);
+
+        // G = (Yc - 0.2627R - 0.0593B) / 0.6780
+        let mut g = y_linear
+            .vsub(r_linear.vmulf(KR))
+            .vsub(b_linear.vmulf(KB))
+            .vdivf(KG)
+            .vmaxf(0.0);
+        transfer.linear_to_scaled_v(&mut [&mut g]);
+
+        (r, g, b)
+    }
+}
+
 mod bt2100 {
     use crate::ColorTransfer;
     use crate::color::primaries::{rgb_to_xyz, xyz_to_rgb};
@@ -518,3 +931,153 @@ mod bt2100 {
         (r, g, b)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bt2020_cl_roundtrip() {
+        let transfer = ColorTransfer::SDR;
+        let rgb_to_xyz = crate::ColorPrimaries::BT2020.rgb_to_xyz_mat();
+        let xyz_to_rgb = crate::ColorPrimaries::BT2020.xyz_to_rgb_mat();
+
+        for rgb in [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.5, 0.5, 0.5],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.2, 0.7, 0.4],
+        ] {
+            let [r, g, b] = rgb;
+
+            let (y, u, v) = unsafe {
+                ColorSpace::BT2020CL.rgb_to_yuv::<f32>(
+                    &[[0.0; 3]; 3],
+                    transfer,
+                    rgb_to_xyz,
+                    0.0,
+                    r,
+                    g,
+                    b,
+                )
+            };
+
+            assert!((-0.5..=0.5).contains(&u), "{rgb:?} u={u}");
+            assert!((-0.5..=0.5).contains(&v), "{rgb:?} v={v}");
+
+            if r == g && g == b {
+                assert!((y - r).abs() < 1e-3, "{rgb:?} y={y}");
+                assert!(u.abs() < 1e-3 && v.abs() < 1e-3);
+            }
+
+            let (r2, g2, b2) = unsafe {
+                ColorSpace::BT2020CL.yuv_to_rgb::<f32>(
+                    &[[0.0; 3]; 3],
+                    transfer,
+                    xyz_to_rgb,
+                    0.0,
+                    y,
+                    u,
+                    v,
+                )
+            };
+
+            // G is reconstructed from linear light, so small errors of the transfer function get amplified
+            assert!((r - r2).abs() < 1e-3, "{rgb:?} -> {:?}", (r2, g2, b2));
+            assert!((g - g2).abs() < 5e-3, "{rgb:?} -> {:?}", (r2, g2, b2));
+            assert!((b - b2).abs() < 1e-3, "{rgb:?} -> {:?}", (r2, g2, b2));
+        }
+    }
+
+    #[test]
+    fn ictcp_subsampled_chroma_is_block_mean() {
+        let rgb_to_xyz = crate::ColorPrimaries::BT2020.rgb_to_xyz_mat();
+
+        let r = [0.9, 0.1, 0.4, 0.7];
+        let g = [0.2, 0.8, 0.5, 0.1];
+        let b = [0.3, 0.6, 0.9, 0.0];
+
+        for (space, transfer) in [
+            (ColorSpace::ICtCpPQ, ColorTransfer::BT2100PQ),
+            (ColorSpace::ICtCpHLG, ColorTransfer::BT2100HLG),
+        ] {
+            let (_, u, v) = unsafe {
+                space.rgbx4_to_yx4_uv::<f32>(&[[0.0; 3]; 3], transfer, rgb_to_xyz, 0.0, r, g, b)
+            };
+
+            let (mut u_sum, mut v_sum) = (0.0, 0.0);
+
+            for i in 0..4 {
+                let (_, u, v) = unsafe {
+                    space.rgb_to_yuv::<f32>(
+                        &[[0.0; 3]; 3],
+                        transfer,
+                        rgb_to_xyz,
+                        0.0,
+                        r[i],
+                        g[i],
+                        b[i],
+                    )
+                };
+
+                u_sum += u;
+                v_sum += v;
+            }
+
+            assert!((u - u_sum / 4.0).abs() < 1e-6, "{space:?} u={u}");
+            assert!((v - v_sum / 4.0).abs() < 1e-6, "{space:?} v={v}");
+        }
+    }
+
+    #[test]
+    fn custom_matches_bt709() {
+        let custom = ColorSpace::Custom(LumaCoefficients::new(0.2126, 0.0722));
+        let transfer = ColorTransfer::SDR;
+        let mat = crate::ColorPrimaries::BT709.rgb_to_xyz_mat();
+
+        let rgb = [0.2, 0.7, 0.4];
+
+        let (y0, u0, v0) = unsafe {
+            ColorSpace::BT709.rgb_to_yuv::<f32>(
+                &BT709_RGB_TO_YUV,
+                transfer,
+                mat,
+                0.0,
+                rgb[0],
+                rgb[1],
+                rgb[2],
+            )
+        };
+        let (y1, u1, v1) = unsafe {
+            custom.rgb_to_yuv::<f32>(
+                &custom.rgb_to_yuv_mat(),
+                transfer,
+                mat,
+                0.0,
+                rgb[0],
+                rgb[1],
+                rgb[2],
+            )
+        };
+
+        assert!((y0 - y1).abs() < 1e-6 && (u0 - u1).abs() < 1e-6 && (v0 - v1).abs() < 1e-6);
+
+        let (r, g, b) = unsafe {
+            custom.yuv_to_rgb::<f32>(&custom.yuv_to_rgb_mat(), transfer, mat, 0.0, y1, u1, v1)
+        };
+
+        assert!(
+            (r - rgb[0]).abs() < 1e-5 && (g - rgb[1]).abs() < 1e-5 && (b - rgb[2]).abs() < 1e-5
+        );
+
+        assert!(custom.validate(8, false).is_ok());
+        assert!(
+            ColorSpace::Custom(LumaCoefficients::new(0.6, 0.5))
+                .validate(8, false)
+                .is_err()
+        );
+    }
+}
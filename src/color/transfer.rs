@@ -17,6 +17,18 @@ pub enum ColorTransfer {
     BT2100PQ,
     /// BT.2100 hybrid log-gamma (HLG)
     BT2100HLG,
+    /// Sony S-Log3
+    SLog3,
+    /// ARRI LogC3 (EI 800)
+    ArriLogC3,
+    /// ARRI LogC4
+    ArriLogC4,
+    /// Panasonic V-Log
+    VLog,
+    /// Canon Log 3
+    CanonLog3,
+    /// Nikon N-Log
+    NLog,
 }
 
 impl ColorTransfer {
@@ -68,6 +80,36 @@ impl ColorTransfer {
                     **v = bt2100_hlg::linear_to_scaled(**v)
                 }
             }
+            ColorTransfer::SLog3 => {
+                for v in i {
+                    **v = s_log3::linear_to_scaled(**v)
+                }
+            }
+            ColorTransfer::ArriLogC3 => {
+                for v in i {
+                    **v = arri_logc3::linear_to_scaled(**v)
+                }
+            }
+            ColorTransfer::ArriLogC4 => {
+                for v in i {
+                    **v = arri_logc4::linear_to_scaled(**v)
+                }
+            }
+            ColorTransfer::VLog => {
+                for v in i {
+                    **v = v_log::linear_to_scaled(**v)
+                }
+            }
+            ColorTransfer::CanonLog3 => {
+                for v in i {
+                    **v = canon_log3::linear_to_scaled(**v)
+                }
+            }
+            ColorTransfer::NLog => {
+                for v in i {
+                    **v = n_log::linear_to_scaled(**v)
+                }
+            }
         }
     }
 
@@ -105,8 +147,62 @@ impl ColorTransfer {
                     **v = bt2100_hlg::scaled_to_linear(**v)
                 }
             }
+            ColorTransfer::SLog3 => {
+                for v in i {
+                    **v = s_log3::scaled_to_linear(**v)
+                }
+            }
+            ColorTransfer::ArriLogC3 => {
+                for v in i {
+                    **v = arri_logc3::scaled_to_linear(**v)
+                }
+            }
+            ColorTransfer::ArriLogC4 => {
+                for v in i {
+                    **v = arri_logc4::scaled_to_linear(**v)
+                }
+            }
+            ColorTransfer::VLog => {
+                for v in i {
+                    **v = v_log::scaled_to_linear(**v)
+                }
+            }
+            ColorTransfer::CanonLog3 => {
+                for v in i {
+                    **v = canon_log3::scaled_to_linear(**v)
+                }
+            }
+            ColorTransfer::NLog => {
+                for v in i {
+                    **v = n_log::scaled_to_linear(**v)
+                }
+            }
         }
     }
+
+    /// Same as [`Self::linear_to_scaled_v`], but mirrors the transfer function for negative values
+    /// (like xvYCC, IEC 61966-2-4) instead of producing undefined results
+    #[inline(always)]
+    pub(crate) unsafe fn linear_to_scaled_mirrored_v<const N: usize, V: Vector>(
+        &self,
+        i: &mut [&mut V; N],
+    ) {
+        let signs = take_signs(i);
+        self.linear_to_scaled_v(i);
+        apply_signs(i, signs);
+    }
+
+    /// Same as [`Self::scaled_to_linear_v`], but mirrors the transfer function for negative values
+    /// (like xvYCC, IEC 61966-2-4) instead of producing undefined results
+    #[inline(always)]
+    pub(crate) unsafe fn scaled_to_linear_mirrored_v<const N: usize, V: Vector>(
+        &self,
+        i: &mut [&mut V; N],
+    ) {
+        let signs = take_signs(i);
+        self.scaled_to_linear_v(i);
+        apply_signs(i, signs);
+    }
 }
 
 mod gamma {
@@ -153,6 +249,26 @@ mod srgb {
     }
 }
 
+/// Make all values positive and return their signs
+#[inline(always)]
+unsafe fn take_signs<const N: usize, V: Vector>(i: &mut [&mut V; N]) -> [V; N] {
+    let mut signs = [V::splat(1.0); N];
+
+    for (v, sign) in i.iter_mut().zip(&mut signs) {
+        *sign = V::select(V::splat(-1.0), V::splat(1.0), v.ltf(0.0));
+        **v = v.vmul(*sign);
+    }
+
+    signs
+}
+
+#[inline(always)]
+unsafe fn apply_signs<const N: usize, V: Vector>(i: &mut [&mut V; N], signs: [V; N]) {
+    for (v, sign) in i.iter_mut().zip(signs) {
+        **v = v.vmul(sign);
+    }
+}
+
 mod sdr {
     use crate::vector::Vector;
 
@@ -267,3 +383,344 @@ pub(crate) mod bt2100_hlg {
         V::select(a, b, mask)
     }
 }
+
+mod s_log3 {
+    use crate::vector::Vector;
+
+    const CUT_LINEAR: f32 = 0.01125;
+    const CUT_SCALED: f32 = 171.210_3 / 1023.0;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_LINEAR);
+
+        // a = (i * (171.2102946929 - 95.0) / 0.01125 + 95.0) / 1023.0
+        let a = i
+            .vmulf((171.210_3 - 95.0) / CUT_LINEAR)
+            .vaddf(95.0)
+            .vdivf(1023.0);
+
+        // b = (420.0 + log10((i + 0.01) / (0.18 + 0.01)) * 261.5) / 1023.0
+        let b = i
+            .vaddf(0.01)
+            .vmaxf(f32::MIN_POSITIVE)
+            .vdivf(0.19)
+            .vln()
+            .vmulf(261.5 / std::f32::consts::LN_10)
+            .vaddf(420.0)
+            .vdivf(1023.0);
+
+        V::select(a, b, mask)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_SCALED);
+
+        // a = (i * 1023.0 - 95.0) * 0.01125 / (171.2102946929 - 95.0)
+        let a = i
+            .vmulf(1023.0)
+            .vsubf(95.0)
+            .vmulf(CUT_LINEAR / (171.210_3 - 95.0));
+
+        // b = 10.0.powf((i * 1023.0 - 420.0) / 261.5) * (0.18 + 0.01) - 0.01
+        let b = V::splat(10.0)
+            .vpow(i.vmulf(1023.0).vsubf(420.0).vdivf(261.5))
+            .vmulf(0.19)
+            .vsubf(0.01);
+
+        V::select(a, b, mask)
+    }
+}
+
+mod arri_logc3 {
+    use crate::vector::Vector;
+
+    const CUT: f32 = 0.010_591;
+    const A: f32 = 5.555_556;
+    const B: f32 = 0.052_272;
+    const C: f32 = 0.247_19;
+    const D: f32 = 0.385_537;
+    const E: f32 = 5.367_655;
+    const F: f32 = 0.092_809;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        let mask = i.lef(CUT);
+
+        // a = E * i + F
+        let a = i.vmulf(E).vaddf(F);
+
+        // b = C * log10(A * i + B) + D
+        let b = i
+            .vmulf(A)
+            .vaddf(B)
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vmulf(C / std::f32::consts::LN_10)
+            .vaddf(D);
+
+        V::select(a, b, mask)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let mask = i.lef(E * CUT + F);
+
+        // a = (i - F) / E
+        let a = i.vsubf(F).vdivf(E);
+
+        // b = (10.0.powf((i - D) / C) - B) / A
+        let b = V::splat(10.0).vpow(i.vsubf(D).vdivf(C)).vsubf(B).vdivf(A);
+
+        V::select(a, b, mask)
+    }
+}
+
+mod arri_logc4 {
+    use crate::vector::Vector;
+    use std::f32::consts::LN_2;
+
+    // a = (2^18 - 16) / 117.45
+    const A: f32 = 2_231.826_3;
+    // b = (1023 - 95) / 1023
+    const B: f32 = 0.907_135_9;
+    // c = 95 / 1023
+    const C: f32 = 0.092_864_13;
+    // s = (7 * ln(2) * 2^(7 - 14 * c / b)) / (a * b)
+    const S: f32 = 0.113_597_21;
+    // t = (2^(14 * (-c / b) + 6) - 64) / a
+    const T: f32 = -0.018_056_996;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        let mask = i.ltf(T);
+
+        // a = (i - T) / S
+        let a = i.vsubf(T).vdivf(S);
+
+        // b = (log2(A * i + 64.0) - 6.0) / 14.0 * B + C
+        let b = i
+            .vmulf(A)
+            .vaddf(64.0)
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vdivf(LN_2)
+            .vsubf(6.0)
+            .vmulf(B / 14.0)
+            .vaddf(C);
+
+        V::select(a, b, mask)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let mask = i.ltf(0.0);
+
+        // a = i * S + T
+        let a = i.vmulf(S).vaddf(T);
+
+        // b = (2.0.powf(14.0 * (i - C) / B + 6.0) - 64.0) / A
+        let b = V::splat(2.0)
+            .vpow(i.vsubf(C).vmulf(14.0 / B).vaddf(6.0))
+            .vsubf(64.0)
+            .vdivf(A);
+
+        V::select(a, b, mask)
+    }
+}
+
+mod v_log {
+    use crate::vector::Vector;
+
+    const CUT_LINEAR: f32 = 0.01;
+    const CUT_SCALED: f32 = 0.181;
+    const B: f32 = 0.008_73;
+    const C: f32 = 0.241_514;
+    const D: f32 = 0.598_206;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_LINEAR);
+
+        // a = 5.6 * i + 0.125
+        let a = i.vmulf(5.6).vaddf(0.125);
+
+        // b = C * log10(i + B) + D
+        let b = i
+            .vaddf(B)
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vmulf(C / std::f32::consts::LN_10)
+            .vaddf(D);
+
+        V::select(a, b, mask)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_SCALED);
+
+        // a = (i - 0.125) / 5.6
+        let a = i.vsubf(0.125).vdivf(5.6);
+
+        // b = 10.0.powf((i - D) / C) - B
+        let b = V::splat(10.0).vpow(i.vsubf(D).vdivf(C)).vsubf(B);
+
+        V::select(a, b, mask)
+    }
+}
+
+mod canon_log3 {
+    use crate::vector::Vector;
+    use std::f32::consts::LN_10;
+
+    // Canon Log 3 is specified in IRE (legal range), these convert it to a full range 10 bit code value
+    const LEGAL_SCALE: f32 = 876.0 / 1023.0;
+    const LEGAL_OFFSET: f32 = 64.0 / 1023.0;
+
+    const CUT_LOW_IRE: f32 = 0.097_465_47;
+    const CUT_HIGH_IRE: f32 = 0.152_778_91;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        // The curve is defined for scene linear values where 0.9 equals a 90% reflectance
+        let i = i.vdivf(0.9);
+
+        let low_mask = i.ltf(-0.014);
+        let mid_mask = i.lef(0.014);
+
+        // a = -0.36726845 * log10(-i * 14.98325 + 1.0) + 0.12783901
+        let a = V::splat(1.0)
+            .vsub(i.vmulf(14.98325))
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vmulf(-0.367_268_45 / LN_10)
+            .vaddf(0.127_839_01);
+
+        // b = 1.9754798 * i + 0.12512219
+        let b = i.vmulf(1.975_479_8).vaddf(0.125_122_19);
+
+        // c = 0.36726845 * log10(i * 14.98325 + 1.0) + 0.12240537
+        let c = i
+            .vmulf(14.98325)
+            .vaddf(1.0)
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vmulf(0.367_268_45 / LN_10)
+            .vaddf(0.122_405_37);
+
+        let ire = V::select(a, V::select(b, c, mid_mask), low_mask);
+
+        ire.vmulf(LEGAL_SCALE).vaddf(LEGAL_OFFSET)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let i = i.vsubf(LEGAL_OFFSET).vdivf(LEGAL_SCALE);
+
+        let low_mask = i.ltf(CUT_LOW_IRE);
+        let mid_mask = i.lef(CUT_HIGH_IRE);
+
+        // a = -(10.0.powf((0.12783901 - i) / 0.36726845) - 1.0) / 14.98325
+        let a = V::splat(1.0)
+            .vsub(V::splat(10.0).vpow(V::splat(0.127_839_01).vsub(i).vdivf(0.367_268_45)))
+            .vdivf(14.98325);
+
+        // b = (i - 0.12512219) / 1.9754798
+        let b = i.vsubf(0.125_122_19).vdivf(1.975_479_8);
+
+        // c = (10.0.powf((i - 0.12240537) / 0.36726845) - 1.0) / 14.98325
+        let c = V::splat(10.0)
+            .vpow(i.vsubf(0.122_405_37).vdivf(0.367_268_45))
+            .vsubf(1.0)
+            .vdivf(14.98325);
+
+        V::select(a, V::select(b, c, mid_mask), low_mask).vmulf(0.9)
+    }
+}
+
+mod n_log {
+    use crate::vector::Vector;
+
+    const CUT_LINEAR: f32 = 0.328;
+    const CUT_SCALED: f32 = 452.0 / 1023.0;
+
+    #[inline(always)]
+    pub(super) unsafe fn linear_to_scaled<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_LINEAR);
+
+        // a = 650.0 * (i + 0.0075).powf(1.0 / 3.0) / 1023.0
+        let a = i
+            .vaddf(0.0075)
+            .vmaxf(0.0)
+            .vpowf(1.0 / 3.0)
+            .vmulf(650.0 / 1023.0);
+
+        // b = (150.0 * i.ln() + 619.0) / 1023.0
+        let b = i
+            .vmaxf(f32::MIN_POSITIVE)
+            .vln()
+            .vmulf(150.0)
+            .vaddf(619.0)
+            .vdivf(1023.0);
+
+        V::select(a, b, mask)
+    }
+
+    #[inline(always)]
+    pub(super) unsafe fn scaled_to_linear<V: Vector>(i: V) -> V {
+        let mask = i.ltf(CUT_SCALED);
+
+        // a = (i * 1023.0 / 650.0).powf(3.0) - 0.0075
+        let a = i.vmaxf(0.0).vmulf(1023.0 / 650.0).vpowf(3.0).vsubf(0.0075);
+
+        // b = E.powf((i * 1023.0 - 619.0) / 150.0)
+        let b = V::splat(std::f32::consts::E).vpow(i.vmulf(1023.0).vsubf(619.0).vdivf(150.0));
+
+        V::select(a, b, mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorTransfer::{self, *};
+
+    const CAMERA_LOG: [ColorTransfer; 6] = [SLog3, ArriLogC3, ArriLogC4, VLog, CanonLog3, NLog];
+
+    #[test]
+    fn camera_log_mid_grey() {
+        // Published code values for 18% grey
+        let expected = [
+            (SLog3, 420.0 / 1023.0),
+            (ArriLogC3, 0.391_007),
+            (ArriLogC4, 0.278_396),
+            (VLog, 0.423_311),
+            (CanonLog3, 0.356_607),
+            (NLog, 0.363_668),
+        ];
+
+        for (transfer, expected) in expected {
+            let scaled = transfer.linear_to_scaled(0.18);
+
+            assert!(
+                (scaled - expected).abs() < 1e-3,
+                "{transfer:?}: expected {expected}, got {scaled}"
+            );
+        }
+    }
+
+    #[test]
+    fn camera_log_roundtrip() {
+        for transfer in CAMERA_LOG {
+            for i in [-0.005, 0.0, 0.005, 0.01, 0.18, 0.5, 1.0, 4.0, 16.0] {
+                let roundtrip = transfer.scaled_to_linear(transfer.linear_to_scaled(i));
+
+                assert!(
+                    (roundtrip - i).abs() <= 1e-4 * i.abs().max(1.0),
+                    "{transfer:?}: {i} roundtripped to {roundtrip}"
+                );
+            }
+        }
+    }
+}
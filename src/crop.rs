@@ -22,6 +22,25 @@ pub struct Window {
     pub height: usize,
 }
 
+/// Returns the `window` or, if there is none, a window covering the whole `width` x `height` image
+///
+/// Returns `None` if the window goes out of the image's bounds.
+pub(crate) fn check_window(width: usize, height: usize, window: Option<Window>) -> Option<Window> {
+    let Some(window) = window else {
+        return Some(Window {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+    };
+
+    let x_end = window.x.checked_add(window.width)?;
+    let y_end = window.y.checked_add(window.height)?;
+
+    (x_end <= width && y_end <= height).then_some(window)
+}
+
 /// Wrapper around [`ImageRef`]/[`ImageMut`] and a [`Window`] cropping the wrapped image
 pub struct Cropped<T>(T, Window);
 
@@ -0,0 +1,112 @@
+use crate::crop::check_window;
+use crate::formats::max_value_for_bits;
+use crate::plane_decs::{Op, plane_layouts};
+use crate::statistics::Channel;
+use crate::{
+    BoundsCheckError, ColorInfo, ConvertError, ImageMut, ImageRefExt, RgbColorInfo, Window,
+    convert_color,
+};
+
+/// Errors that may occur when calling [`fill`] or [`clear`]
+#[derive(Debug, thiserror::Error)]
+pub enum FillError {
+    #[error("the given window goes out of the image's bounds")]
+    WindowOutOfBounds,
+
+    #[error(transparent)]
+    BoundsCheck(#[from] BoundsCheckError),
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+}
+
+/// Fill `dst`, or the `window` inside of it, with a solid color.
+///
+/// `value` are the code values of the color in `color` with `bits` bits per component, in the order Y, U, V or
+/// R, G, B (see [`convert_color`]). They are converted to the color of `dst`, quantized and written directly into
+/// its planes. Alpha is set to fully opaque.
+///
+/// Subsampled chroma samples are written if any of their pixels is inside the `window`, a window with even
+/// coordinates and dimensions never changes pixels outside of it.
+pub fn fill(
+    dst: &mut dyn ImageMut,
+    color: &ColorInfo,
+    bits: usize,
+    value: [f32; 3],
+    window: Option<Window>,
+) -> Result<(), FillError> {
+    dst.bounds_check()?;
+
+    let window =
+        check_window(dst.width(), dst.height(), window).ok_or(FillError::WindowOutOfBounds)?;
+
+    let format = dst.format();
+    let dst_bits = format.bits_per_component();
+    let max_value = max_value_for_bits(dst_bits);
+
+    let [c0, c1, c2] = convert_color(color, bits, &dst.color(), dst_bits, value)?;
+    let [c0, c1, c2] = [c0, c1, c2].map(|c| c.round().clamp(0.0, max_value) as u16);
+
+    let sample = |channel: Channel| match channel {
+        Channel::Y | Channel::R => c0,
+        Channel::U | Channel::G => c1,
+        Channel::V | Channel::B => c2,
+        Channel::A => max_value as u16,
+    };
+
+    for (((plane, stride), desc), layout) in dst
+        .planes_mut()
+        .zip(format.plane_desc())
+        .zip(plane_layouts(format))
+    {
+        let rows = match desc.height_op {
+            Op::Div(n) => window.y / n..(window.y + window.height).div_ceil(n),
+            _ => window.y..window.y + window.height,
+        };
+
+        let group_len = layout.primitives.len();
+        let groups = window.x / layout.width..(window.x + window.width).div_ceil(layout.width);
+
+        for row in rows {
+            let row = &mut plane[row * stride..];
+
+            for group in groups.clone() {
+                let group_x = group * layout.width;
+
+                for (i, &(channel, start, end)) in layout.primitives.iter().enumerate() {
+                    // Skip samples whose pixels are all outside of the window
+                    if group_x + end <= window.x || group_x + start >= window.x + window.width {
+                        continue;
+                    }
+
+                    let index = group * group_len + i;
+                    let value = sample(channel);
+
+                    match desc.bytes_per_primitive {
+                        1 => row[index] = value as u8,
+                        _ => row[index * 2..index * 2 + 2].copy_from_slice(&value.to_ne_bytes()),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Clear `dst`, or the `window` inside of it, to black.
+///
+/// See [`fill`] for details.
+pub fn clear(dst: &mut dyn ImageMut, window: Option<Window>) -> Result<(), FillError> {
+    let color = dst.color();
+
+    // Black is the same in every transfer function and primaries
+    let black = ColorInfo::RGB(RgbColorInfo {
+        transfer: color.transfer(),
+        primaries: color.primaries(),
+        full_range: true,
+        premultiplied_alpha: false,
+    });
+
+    fill(dst, &black, 8, [0.0; 3], window)
+}
@@ -0,0 +1,142 @@
+use super::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::color::ColorInfo;
+use crate::vector::Vector;
+use crate::{AlphaBackground, ColorTransfer, ConvertOptions};
+
+/// Composites pixels onto a background color or checkerboard, the resulting pixels are fully opaque
+pub(crate) struct AlphaComposite<S> {
+    /// Transfer function of the source, set when blending in linear light
+    transfer: Option<ColorTransfer>,
+    preserve_out_of_range: bool,
+
+    colors: [[f32; 3]; 2],
+    /// Checkerboard square size, 0 for a solid background
+    checker_size: usize,
+
+    /// Vertical offset of the image inside the checkerboard
+    y_offset: usize,
+
+    src: S,
+}
+
+impl<S> AlphaComposite<S> {
+    pub(crate) fn new(
+        src_color: &ColorInfo,
+        background: AlphaBackground,
+        options: &ConvertOptions,
+        y_offset: usize,
+        src: S,
+    ) -> Self {
+        let (mut colors, checker_size) = match background {
+            AlphaBackground::Color(color) => ([color, color], 0),
+            AlphaBackground::Checkerboard { size, colors } => (colors, size.max(1)),
+        };
+
+        let transfer = options
+            .composite_in_linear_light
+            .then(|| src_color.transfer());
+
+        if let Some(transfer) = transfer {
+            for c in colors.iter_mut().flatten() {
+                *c = transfer.scaled_to_linear(*c);
+            }
+        }
+
+        Self {
+            transfer,
+            preserve_out_of_range: options.preserve_out_of_range,
+            colors,
+            checker_size,
+            y_offset,
+            src,
+        }
+    }
+
+    /// Returns 0.0 for pixels on the first and 1.0 for pixels on the second checkerboard color
+    #[inline(always)]
+    unsafe fn checker<V: Vector>(&self, x: usize, y: usize) -> V {
+        let size = self.checker_size as f32;
+
+        // floor((x + 0.5) / size) can never be a tie for vround
+        let column = V::lane_indices()
+            .vaddf(x as f32 + 0.5)
+            .vdivf(size)
+            .vsubf(0.5)
+            .vround();
+
+        let n = column.vaddf(((y + self.y_offset) / self.checker_size) as f32);
+
+        // n - 2 * floor(n / 2)
+        n.vsub(n.vmulf(0.5).vsubf(0.25).vround().vmulf(2.0))
+    }
+
+    #[inline(always)]
+    unsafe fn composite<V: Vector>(&self, px: RgbaPixel<V>, x: usize, y: usize) -> RgbaPixel<V> {
+        let [c0, c1] = self.colors;
+
+        let [bg_r, bg_g, bg_b] = if self.checker_size == 0 {
+            c0.map(|c| V::splat(c))
+        } else {
+            let t = self.checker::<V>(x, y);
+
+            [0, 1, 2].map(|i| t.vmulf(c1[i] - c0[i]).vaddf(c0[i]))
+        };
+
+        RgbaPixel {
+            r: px.r.vsub(bg_r).vmul(px.a).vadd(bg_r),
+            g: px.g.vsub(bg_g).vmul(px.a).vadd(bg_g),
+            b: px.b.vsub(bg_b).vmul(px.a).vadd(bg_b),
+            a: V::splat(1.0),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn apply_transfer<V: Vector>(&self, block: &mut RgbaBlock<V>, to_linear: bool) {
+        let Some(transfer) = self.transfer else {
+            return;
+        };
+
+        let mut i = [
+            &mut block.px00.r,
+            &mut block.px00.g,
+            &mut block.px00.b,
+            &mut block.px01.r,
+            &mut block.px01.g,
+            &mut block.px01.b,
+            &mut block.px10.r,
+            &mut block.px10.g,
+            &mut block.px10.b,
+            &mut block.px11.r,
+            &mut block.px11.g,
+            &mut block.px11.b,
+        ];
+
+        match (to_linear, self.preserve_out_of_range) {
+            (true, true) => transfer.scaled_to_linear_mirrored_v(&mut i),
+            (true, false) => transfer.scaled_to_linear_v(&mut i),
+            (false, true) => transfer.linear_to_scaled_mirrored_v(&mut i),
+            (false, false) => transfer.linear_to_scaled_v(&mut i),
+        }
+    }
+}
+
+impl<S: RgbaSrc> RgbaSrc for AlphaComposite<S> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let mut block = self.src.read::<V>(x, y);
+
+        self.apply_transfer(&mut block, true);
+
+        // px00 & px10 hold the first V::LEN pixels of the rows, px01 & px11 the following ones
+        let mut block = RgbaBlock {
+            px00: self.composite(block.px00, x, y),
+            px01: self.composite(block.px01, x + V::LEN, y),
+            px10: self.composite(block.px10, x, y + 1),
+            px11: self.composite(block.px11, x + V::LEN, y + 1),
+        };
+
+        self.apply_transfer(&mut block, false);
+
+        block
+    }
+}
@@ -0,0 +1,83 @@
+use super::rgb::{RgbaBlock, RgbaSrc};
+use crate::color::ColorInfo;
+use crate::vector::Vector;
+use crate::{ColorTransfer, ConvertOptions, Lut3D, LutInterpolation};
+
+/// Applies a [`Lut3D`] to the RGB values of the destination color
+pub(crate) struct ApplyLut<'a, S> {
+    lut: &'a Lut3D,
+    interpolation: LutInterpolation,
+
+    /// Transfer function of the destination, set when applying the LUT in linear light
+    transfer: Option<ColorTransfer>,
+    preserve_out_of_range: bool,
+
+    src: S,
+}
+
+impl<'a, S> ApplyLut<'a, S> {
+    pub(crate) fn new(
+        lut: &'a Lut3D,
+        dst_color: &ColorInfo,
+        options: &ConvertOptions,
+        src: S,
+    ) -> Self {
+        Self {
+            lut,
+            interpolation: options.lut_interpolation,
+            transfer: options.lut_in_linear_light.then(|| dst_color.transfer()),
+            preserve_out_of_range: options.preserve_out_of_range,
+            src,
+        }
+    }
+}
+
+impl<S: RgbaSrc> RgbaSrc for ApplyLut<'_, S> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let mut block = self.src.read(x, y);
+
+        let mut i = [
+            &mut block.px00.r,
+            &mut block.px00.g,
+            &mut block.px00.b,
+            &mut block.px01.r,
+            &mut block.px01.g,
+            &mut block.px01.b,
+            &mut block.px10.r,
+            &mut block.px10.g,
+            &mut block.px10.b,
+            &mut block.px11.r,
+            &mut block.px11.g,
+            &mut block.px11.b,
+        ];
+
+        if let Some(transfer) = self.transfer {
+            if self.preserve_out_of_range {
+                transfer.scaled_to_linear_mirrored_v(&mut i);
+            } else {
+                transfer.scaled_to_linear_v(&mut i);
+            }
+        }
+
+        let mut iter = i.chunks_exact_mut(3);
+
+        while let Some([r, g, b]) = iter.next() {
+            let (r_, g_, b_) = self.lut.apply_v(self.interpolation, **r, **g, **b);
+
+            **r = r_;
+            **g = g_;
+            **b = b_;
+        }
+
+        if let Some(transfer) = self.transfer {
+            if self.preserve_out_of_range {
+                transfer.linear_to_scaled_mirrored_v(&mut i);
+            } else {
+                transfer.linear_to_scaled_v(&mut i);
+            }
+        }
+
+        block
+    }
+}
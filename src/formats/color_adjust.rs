@@ -0,0 +1,98 @@
+use super::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::ColorAdjustments;
+use crate::color::ColorInfo;
+use crate::vector::Vector;
+
+/// Applies [`ColorAdjustments`] to the RGB values of the destination color
+pub(crate) struct ColorAdjust<S> {
+    /// White balance, saturation and hue combined into a single matrix, `matrix[output][input]`
+    matrix: [[f32; 3]; 3],
+
+    contrast: f32,
+    /// Brightness and the offset to scale around mid-gray
+    offset: f32,
+
+    /// Inverse of the gamma, none if neutral
+    inv_gamma: Option<f32>,
+
+    src: S,
+}
+
+impl<S> ColorAdjust<S> {
+    pub(crate) fn new(adjustments: &ColorAdjustments, dst_color: &ColorInfo, src: S) -> Self {
+        // Luminance weights of the destination primaries
+        let rgb_to_xyz = dst_color.primaries().rgb_to_xyz_mat();
+        let [kr, kg, kb] = [rgb_to_xyz[0][1], rgb_to_xyz[1][1], rgb_to_xyz[2][1]];
+
+        let (sin, cos) = adjustments.hue.to_radians().sin_cos();
+        let saturation = adjustments.saturation;
+
+        // Rotate and scale the chroma in a color difference space, leaving the luminance untouched
+        let adjust_chroma = |[r, g, b]: [f32; 3]| {
+            let y = kr * r + kg * g + kb * b;
+            let cb = (b - y) / (2.0 - 2.0 * kb);
+            let cr = (r - y) / (2.0 - 2.0 * kr);
+
+            let cb_ = saturation * (cos * cb - sin * cr);
+            let cr_ = saturation * (sin * cb + cos * cr);
+
+            let r = y + (2.0 - 2.0 * kr) * cr_;
+            let b = y + (2.0 - 2.0 * kb) * cb_;
+            let g = (y - kr * r - kb * b) / kg;
+
+            [r, g, b]
+        };
+
+        // Transform every basis vector to get the columns of the matrix, scaled by the white balance gains
+        let columns = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+            .map(adjust_chroma)
+            .into_iter()
+            .zip(adjustments.white_balance)
+            .map(|(column, gain)| column.map(|v| v * gain));
+
+        let mut matrix = [[0.0; 3]; 3];
+
+        for (input, column) in columns.enumerate() {
+            for (output, v) in column.into_iter().enumerate() {
+                matrix[output][input] = v;
+            }
+        }
+
+        Self {
+            matrix,
+            contrast: adjustments.contrast,
+            offset: 0.5 - 0.5 * adjustments.contrast + adjustments.brightness,
+            inv_gamma: (adjustments.gamma != 1.0).then(|| 1.0 / adjustments.gamma),
+            src,
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn adjust<V: Vector>(&self, px: RgbaPixel<V>) -> RgbaPixel<V> {
+        let [r, g, b] = self.matrix.map(|[mr, mg, mb]| {
+            let v = px.r.vmulf(mr).vadd(px.g.vmulf(mg)).vadd(px.b.vmulf(mb));
+            let v = v.vmulf(self.contrast).vaddf(self.offset);
+
+            match self.inv_gamma {
+                Some(inv_gamma) => v.vmaxf(0.0).vpowf(inv_gamma),
+                None => v,
+            }
+        });
+
+        RgbaPixel { r, g, b, a: px.a }
+    }
+}
+
+impl<S: RgbaSrc> RgbaSrc for ColorAdjust<S> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let block = self.src.read(x, y);
+
+        RgbaBlock {
+            px00: self.adjust(block.px00),
+            px01: self.adjust(block.px01),
+            px10: self.adjust(block.px10),
+            px11: self.adjust(block.px11),
+        }
+    }
+}
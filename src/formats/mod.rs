@@ -1,6 +1,10 @@
+mod alpha_composite;
+mod apply_lut;
+mod color_adjust;
 mod dyn_rgba_src;
+mod solid;
 mod transfer_and_primaries_convert;
-mod visit_2x2;
+pub(crate) mod visit_2x2;
 
 #[cfg(any(
     feature = "I420",
@@ -18,12 +22,18 @@ pub(crate) mod yuv444;
 
 pub(crate) mod rgb;
 
+pub(crate) mod yuv_range;
+
 pub(crate) use transfer_and_primaries_convert::{
     TransferAndPrimariesConvert, need_transfer_and_primaries_convert,
 };
 
+pub(crate) use alpha_composite::AlphaComposite;
+pub(crate) use apply_lut::ApplyLut;
+pub(crate) use color_adjust::ColorAdjust;
 pub(crate) use dyn_rgba_src::{DynRgbaReader, DynRgbaReaderSpec};
+pub(crate) use solid::Solid;
 
-fn max_value_for_bits(bits: usize) -> f32 {
+pub(crate) fn max_value_for_bits(bits: usize) -> f32 {
     ((1 << bits) - 1) as f32
 }
@@ -1,4 +1,5 @@
 use crate::vector::Vector;
+use crate::{ColorInfo, RgbColorInfo};
 
 mod read_rgb;
 mod read_rgba;
@@ -40,6 +41,31 @@ impl<V: Vector> RgbaPixel<V> {
         Self { r, g, b, a }
     }
 
+    /// Multiply the color components with alpha
+    #[inline(always)]
+    pub(crate) unsafe fn premultiply(self) -> Self {
+        Self {
+            r: self.r.vmul(self.a),
+            g: self.g.vmul(self.a),
+            b: self.b.vmul(self.a),
+            a: self.a,
+        }
+    }
+
+    /// Divide the color components by alpha, fully transparent pixels become black
+    #[inline(always)]
+    pub(crate) unsafe fn unpremultiply(self) -> Self {
+        let transparent = self.a.lef(0.0);
+        let zero = V::splat(0.0);
+
+        Self {
+            r: V::select(zero, self.r.vdiv(self.a), transparent),
+            g: V::select(zero, self.g.vdiv(self.a), transparent),
+            b: V::select(zero, self.b.vdiv(self.a), transparent),
+            a: self.a,
+        }
+    }
+
     #[inline(always)]
     pub(crate) unsafe fn from_components<const SWIZZLE: u8>(c0: V, c1: V, c2: V, c3: V) -> Self {
         match SWIZZLE {
@@ -63,3 +89,70 @@ pub(crate) struct RgbaBlock<V> {
 pub(crate) trait RgbaSrc {
     unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V>;
 }
+
+/// Quantization of normalized RGB values into the code values of a format with a given bit depth
+///
+/// Limited (studio swing) range maps RGB to 16..=235 for 8-bit, scaled by `2^(N-8)` for higher bit depths.
+/// Alpha is always full range.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RgbRange {
+    offset: f32,
+    scale: f32,
+    max_value: f32,
+}
+
+impl RgbRange {
+    pub(crate) fn new(color: &ColorInfo, bits_per_component: usize) -> Self {
+        let max_value = super::max_value_for_bits(bits_per_component);
+
+        if let ColorInfo::RGB(RgbColorInfo {
+            full_range: false, ..
+        }) = color
+        {
+            let step = (1u32 << bits_per_component) as f32 / 256.0;
+
+            Self {
+                offset: 16.0 * step,
+                scale: 219.0 * step,
+                max_value,
+            }
+        } else {
+            Self {
+                offset: 0.0,
+                scale: max_value,
+                max_value,
+            }
+        }
+    }
+
+    /// Map RGB code values to 0..=1, alpha is left untouched
+    #[inline(always)]
+    pub(crate) unsafe fn decode_rgb<V: Vector>(&self, px: RgbaPixel<V>) -> RgbaPixel<V> {
+        RgbaPixel {
+            r: px.r.vsubf(self.offset).vdivf(self.scale),
+            g: px.g.vsubf(self.offset).vdivf(self.scale),
+            b: px.b.vsubf(self.offset).vdivf(self.scale),
+            a: px.a,
+        }
+    }
+
+    /// Map RGBA code values to 0..=1
+    #[inline(always)]
+    pub(crate) unsafe fn decode_rgba<V: Vector>(&self, px: RgbaPixel<V>) -> RgbaPixel<V> {
+        RgbaPixel {
+            a: px.a.vdivf(self.max_value),
+            ..self.decode_rgb(px)
+        }
+    }
+
+    /// Map RGBA values from 0..=1 to code values
+    #[inline(always)]
+    pub(crate) unsafe fn encode_rgba<V: Vector>(&self, px: RgbaPixel<V>) -> RgbaPixel<V> {
+        RgbaPixel {
+            r: px.r.vmulf(self.scale).vaddf(self.offset),
+            g: px.g.vmulf(self.scale).vaddf(self.offset),
+            b: px.b.vmulf(self.scale).vaddf(self.offset),
+            a: px.a.vmulf(self.max_value),
+        }
+    }
+}
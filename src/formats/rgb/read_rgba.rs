@@ -1,4 +1,4 @@
-use super::{RgbaBlock, RgbaPixel, RgbaSrc};
+use super::{RgbRange, RgbaBlock, RgbaPixel, RgbaSrc};
 use crate::planes::read_planes;
 use crate::primitive::Primitive;
 use crate::vector::Vector;
@@ -10,7 +10,8 @@ pub(crate) struct ReadRgba<'a, const SWIZZLE: u8, P: Primitive> {
 
     rgba_stride: usize,
 
-    max_value: f32,
+    range: RgbRange,
+    premultiplied_alpha: bool,
 
     _m: PhantomData<&'a [P]>,
 }
@@ -24,7 +25,8 @@ impl<'a, const SWIZZLE: u8, P: Primitive> ReadRgba<'a, SWIZZLE, P> {
         Ok(Self {
             rgba,
             rgba_stride,
-            max_value: crate::formats::max_value_for_bits(src.format().bits_per_component()),
+            range: RgbRange::new(&src.color(), src.format().bits_per_component()),
+            premultiplied_alpha: src.color().premultiplied_alpha(),
             _m: PhantomData,
         })
     }
@@ -41,36 +43,28 @@ impl<const SWIZZLE: u8, P: Primitive> RgbaSrc for ReadRgba<'_, SWIZZLE, P> {
         let [[r10, g10, b10, a10], [r11, g11, b11, a11]] =
             P::load_4x_interleaved_2x::<V>(&self.rgba[rgba10offset..]);
 
-        let r00 = r00.vdivf(self.max_value);
-        let g00 = g00.vdivf(self.max_value);
-        let b00 = b00.vdivf(self.max_value);
-        let a00 = a00.vdivf(self.max_value);
-
-        let r01 = r01.vdivf(self.max_value);
-        let g01 = g01.vdivf(self.max_value);
-        let b01 = b01.vdivf(self.max_value);
-        let a01 = a01.vdivf(self.max_value);
-
-        let r10 = r10.vdivf(self.max_value);
-        let g10 = g10.vdivf(self.max_value);
-        let b10 = b10.vdivf(self.max_value);
-        let a10 = a10.vdivf(self.max_value);
-
-        let r11 = r11.vdivf(self.max_value);
-        let g11 = g11.vdivf(self.max_value);
-        let b11 = b11.vdivf(self.max_value);
-        let a11 = a11.vdivf(self.max_value);
-
         let px00 = RgbaPixel::from_components::<SWIZZLE>(r00, g00, b00, a00);
         let px01 = RgbaPixel::from_components::<SWIZZLE>(r01, g01, b01, a01);
         let px10 = RgbaPixel::from_components::<SWIZZLE>(r10, g10, b10, a10);
         let px11 = RgbaPixel::from_components::<SWIZZLE>(r11, g11, b11, a11);
 
-        RgbaBlock {
-            px00,
-            px01,
-            px10,
-            px11,
+        let block = RgbaBlock {
+            px00: self.range.decode_rgba(px00),
+            px01: self.range.decode_rgba(px01),
+            px10: self.range.decode_rgba(px10),
+            px11: self.range.decode_rgba(px11),
+        };
+
+        // The rest of the pipeline always operates on straight alpha
+        if self.premultiplied_alpha {
+            RgbaBlock {
+                px00: block.px00.unpremultiply(),
+                px01: block.px01.unpremultiply(),
+                px10: block.px10.unpremultiply(),
+                px11: block.px11.unpremultiply(),
+            }
+        } else {
+            block
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::formats::rgb::{RgbaPixel, RgbaSrc, SWIZZLE_BGRA, SWIZZLE_RGBA};
+use crate::formats::rgb::{RgbRange, RgbaPixel, RgbaSrc, SWIZZLE_BGRA, SWIZZLE_RGBA};
 use crate::formats::visit_2x2::{Image2x2Visitor, visit};
 use crate::planes::read_planes_mut;
 use crate::primitive::Primitive;
@@ -15,7 +15,7 @@ where
 
     rgb_stride: usize,
 
-    max_value: f32,
+    range: RgbRange,
 
     rgba_src: S,
 
@@ -33,6 +33,7 @@ where
         let dst_width = dst.width();
         let dst_height = dst.height();
         let dst_format = dst.format();
+        let dst_color = dst.color();
 
         let [(rgb, rgb_stride)] = read_planes_mut(dst.planes_mut())?;
 
@@ -42,7 +43,7 @@ where
             Self {
                 rgb,
                 rgb_stride,
-                max_value: crate::formats::max_value_for_bits(dst_format.bits_per_component()),
+                range: RgbRange::new(&dst_color, dst_format.bits_per_component()),
                 rgba_src,
                 _m: PhantomData,
             },
@@ -67,16 +68,16 @@ where
         P::write_interleaved_3x_2x(
             &mut self.rgb[offset00..],
             [
-                multiply_and_reverse::<SWIZZLE, V>(block.px00, self.max_value),
-                multiply_and_reverse::<SWIZZLE, V>(block.px01, self.max_value),
+                multiply_and_reverse::<SWIZZLE, V>(block.px00, self.range),
+                multiply_and_reverse::<SWIZZLE, V>(block.px01, self.range),
             ],
         );
 
         P::write_interleaved_3x_2x(
             &mut self.rgb[offset10..],
             [
-                multiply_and_reverse::<SWIZZLE, V>(block.px10, self.max_value),
-                multiply_and_reverse::<SWIZZLE, V>(block.px11, self.max_value),
+                multiply_and_reverse::<SWIZZLE, V>(block.px10, self.range),
+                multiply_and_reverse::<SWIZZLE, V>(block.px11, self.range),
             ],
         );
     }
@@ -85,11 +86,9 @@ where
 #[inline(always)]
 unsafe fn multiply_and_reverse<const SWIZZLE: u8, V: Vector>(
     px: RgbaPixel<V>,
-    max_value: f32,
+    range: RgbRange,
 ) -> [V; 3] {
-    let r = px.r.vmulf(max_value);
-    let g = px.g.vmulf(max_value);
-    let b = px.b.vmulf(max_value);
+    let RgbaPixel { r, g, b, .. } = range.encode_rgba(px);
 
     match SWIZZLE {
         SWIZZLE_RGBA => [r, g, b],
@@ -1,4 +1,4 @@
-use super::{RgbaPixel, RgbaSrc};
+use super::{RgbRange, RgbaPixel, RgbaSrc};
 use crate::formats::visit_2x2::{Image2x2Visitor, visit};
 use crate::planes::read_planes_mut;
 use crate::primitive::Primitive;
@@ -15,7 +15,8 @@ where
 
     rgba_stride: usize,
 
-    max_value: f32,
+    range: RgbRange,
+    premultiplied_alpha: bool,
 
     rgba_src: S,
 
@@ -33,6 +34,7 @@ where
         let dst_width = dst.width();
         let dst_height = dst.height();
         let dst_format = dst.format();
+        let dst_color = dst.color();
 
         let [(rgba, rgba_stride)] = read_planes_mut(dst.planes_mut())?;
 
@@ -42,7 +44,8 @@ where
             Self {
                 rgba,
                 rgba_stride,
-                max_value: crate::formats::max_value_for_bits(dst_format.bits_per_component()),
+                range: RgbRange::new(&dst_color, dst_format.bits_per_component()),
+                premultiplied_alpha: dst_color.premultiplied_alpha(),
                 rgba_src,
                 _m: PhantomData,
             },
@@ -59,7 +62,14 @@ where
 {
     #[inline(always)]
     unsafe fn visit<V: Vector>(&mut self, x: usize, y: usize) {
-        let block = self.rgba_src.read::<V>(x, y);
+        let mut block = self.rgba_src.read::<V>(x, y);
+
+        if self.premultiplied_alpha {
+            block.px00 = block.px00.premultiply();
+            block.px01 = block.px01.premultiply();
+            block.px10 = block.px10.premultiply();
+            block.px11 = block.px11.premultiply();
+        }
 
         let offset00 = y * self.rgba_stride + x * 4 * P::SIZE;
         let offset10 = (y + 1) * self.rgba_stride + x * 4 * P::SIZE;
@@ -67,16 +77,16 @@ where
         P::write_interleaved_4x_2x(
             &mut self.rgba[offset00..],
             [
-                multiply_and_reverse::<SWIZZLE, V>(block.px00, self.max_value),
-                multiply_and_reverse::<SWIZZLE, V>(block.px01, self.max_value),
+                multiply_and_reverse::<SWIZZLE, V>(block.px00, self.range),
+                multiply_and_reverse::<SWIZZLE, V>(block.px01, self.range),
             ],
         );
 
         P::write_interleaved_4x_2x(
             &mut self.rgba[offset10..],
             [
-                multiply_and_reverse::<SWIZZLE, V>(block.px10, self.max_value),
-                multiply_and_reverse::<SWIZZLE, V>(block.px11, self.max_value),
+                multiply_and_reverse::<SWIZZLE, V>(block.px10, self.range),
+                multiply_and_reverse::<SWIZZLE, V>(block.px11, self.range),
             ],
         );
     }
@@ -85,12 +95,9 @@ where
 #[inline(always)]
 unsafe fn multiply_and_reverse<const SWIZZLE: u8, V: Vector>(
     px: RgbaPixel<V>,
-    max_value: f32,
+    range: RgbRange,
 ) -> [V; 4] {
-    let r = px.r.vmulf(max_value);
-    let g = px.g.vmulf(max_value);
-    let b = px.b.vmulf(max_value);
-    let a = px.a.vmulf(max_value);
+    let RgbaPixel { r, g, b, a } = range.encode_rgba(px);
 
     match SWIZZLE {
         super::SWIZZLE_RGBA => [r, g, b, a],
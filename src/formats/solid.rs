@@ -0,0 +1,34 @@
+use super::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::vector::Vector;
+
+/// Source yielding the same normalized RGBA value for every pixel
+pub(crate) struct Solid {
+    rgba: [f32; 4],
+}
+
+impl Solid {
+    pub(crate) fn new(rgba: [f32; 4]) -> Self {
+        Self { rgba }
+    }
+}
+
+impl RgbaSrc for Solid {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, _x: usize, _y: usize) -> RgbaBlock<V> {
+        let [r, g, b, a] = self.rgba;
+
+        let px = RgbaPixel {
+            r: V::splat(r),
+            g: V::splat(g),
+            b: V::splat(b),
+            a: V::splat(a),
+        };
+
+        RgbaBlock {
+            px00: px,
+            px01: px,
+            px10: px,
+            px11: px,
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use super::rgb::{RgbaBlock, RgbaSrc};
-use crate::ColorTransfer;
 use crate::color::ColorInfo;
 use crate::color::primaries::{rgb_to_xyz, xyz_to_rgb};
 use crate::vector::Vector;
+use crate::{ColorTransfer, ConvertOptions};
 
 pub(crate) struct TransferAndPrimariesConvert<S> {
     rgb_to_xyz: &'static [[f32; 3]; 3],
@@ -11,6 +11,8 @@ pub(crate) struct TransferAndPrimariesConvert<S> {
     src_transfer: ColorTransfer,
     dst_transfer: ColorTransfer,
 
+    preserve_out_of_range: bool,
+
     src: S,
 }
 
@@ -22,12 +24,18 @@ pub(crate) fn need_transfer_and_primaries_convert(
 }
 
 impl<S> TransferAndPrimariesConvert<S> {
-    pub(crate) fn new(src_color: &ColorInfo, dst_color: &ColorInfo, src: S) -> Self {
+    pub(crate) fn new(
+        src_color: &ColorInfo,
+        dst_color: &ColorInfo,
+        options: &ConvertOptions,
+        src: S,
+    ) -> Self {
         Self {
             rgb_to_xyz: src_color.primaries().rgb_to_xyz_mat(),
             xyz_to_rgb: dst_color.primaries().xyz_to_rgb_mat(),
             src_transfer: src_color.transfer(),
             dst_transfer: dst_color.transfer(),
+            preserve_out_of_range: options.preserve_out_of_range,
             src,
         }
     }
@@ -53,7 +61,11 @@ impl<S: RgbaSrc> RgbaSrc for TransferAndPrimariesConvert<S> {
             &mut block.px11.b,
         ];
 
-        self.src_transfer.scaled_to_linear_v(&mut i);
+        if self.preserve_out_of_range {
+            self.src_transfer.scaled_to_linear_mirrored_v(&mut i);
+        } else {
+            self.src_transfer.scaled_to_linear_v(&mut i);
+        }
 
         let mut iter = i.chunks_exact_mut(3);
 
@@ -67,7 +79,11 @@ impl<S: RgbaSrc> RgbaSrc for TransferAndPrimariesConvert<S> {
             **b = b_;
         }
 
-        self.dst_transfer.linear_to_scaled_v(&mut i);
+        if self.preserve_out_of_range {
+            self.dst_transfer.linear_to_scaled_mirrored_v(&mut i);
+        } else {
+            self.dst_transfer.linear_to_scaled_v(&mut i);
+        }
 
         block
     }
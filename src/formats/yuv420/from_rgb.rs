@@ -1,6 +1,7 @@
 use super::{Yuv420Block, Yuv420Src};
 use crate::color::ColorInfo;
 use crate::formats::rgb::{RgbaBlock, RgbaSrc};
+use crate::formats::yuv_range::YuvRange;
 use crate::vector::Vector;
 use crate::{ColorSpace, ColorTransfer, ConvertError};
 
@@ -8,23 +9,33 @@ use crate::{ColorSpace, ColorTransfer, ConvertError};
 pub(crate) struct FromRgb<S> {
     rgba_src: S,
     space: ColorSpace,
+    rgb_to_yuv: [[f32; 3]; 3],
     transfer: ColorTransfer,
     rgb_to_xyz: &'static [[f32; 3]; 3],
-    full_range: bool,
+    range: YuvRange,
+    max_value: f32,
 }
 
 impl<S: RgbaSrc> FromRgb<S> {
-    pub(crate) fn new(color: &ColorInfo, rgba_src: S) -> Result<Self, ConvertError> {
+    pub(crate) fn new(
+        color: &ColorInfo,
+        bits_per_component: usize,
+        rgba_src: S,
+    ) -> Result<Self, ConvertError> {
         let ColorInfo::YUV(yuv) = color else {
             return Err(ConvertError::InvalidColorInfo);
         };
 
+        yuv.space.validate(bits_per_component, yuv.full_range)?;
+
         Ok(Self {
             rgba_src,
             space: yuv.space,
+            rgb_to_yuv: yuv.space.rgb_to_yuv_mat(),
             transfer: yuv.transfer,
             rgb_to_xyz: yuv.primaries.rgb_to_xyz_mat(),
-            full_range: yuv.full_range,
+            range: YuvRange::new(bits_per_component, yuv.full_range),
+            max_value: crate::formats::max_value_for_bits(bits_per_component),
         })
     }
 }
@@ -40,43 +51,22 @@ impl<S: RgbaSrc> Yuv420Src for FromRgb<S> {
         } = self.rgba_src.read::<V>(x, y);
 
         let ([y00, y01, y10, y11], u, v) = self.space.rgbx4_to_yx4_uv(
+            &self.rgb_to_yuv,
             self.transfer,
             self.rgb_to_xyz,
+            self.max_value,
             [px00.r, px01.r, px10.r, px11.r],
             [px00.g, px01.g, px10.g, px11.g],
             [px00.b, px01.b, px10.b, px11.b],
         );
 
-        // U & V scales from -0.5..=0.5, so bring that up into 0..=1
-        let u = u.vaddf(0.5);
-        let v = v.vaddf(0.5);
-
-        let (y00, y01, y10, y11, u, v) = if self.full_range {
-            (y00, y01, y10, y11, u, v)
-        } else {
-            let v16 = V::splat(16.0 / 255.0);
-
-            let y_scale = V::splat(219.0 / 255.0);
-            let uv_scale = V::splat(224.0 / 255.0);
-
-            let y00 = v16.vadd(y00.vmul(y_scale));
-            let y01 = v16.vadd(y01.vmul(y_scale));
-            let y10 = v16.vadd(y10.vmul(y_scale));
-            let y11 = v16.vadd(y11.vmul(y_scale));
-
-            let u = v16.vadd(u.vmul(uv_scale));
-            let v = v16.vadd(v.vmul(uv_scale));
-
-            (y00, y01, y10, y11, u, v)
-        };
-
         Yuv420Block {
-            y00,
-            y01,
-            y10,
-            y11,
-            u,
-            v,
+            y00: self.range.encode_y(y00),
+            y01: self.range.encode_y(y01),
+            y10: self.range.encode_y(y10),
+            y11: self.range.encode_y(y11),
+            u: self.range.encode_uv(u),
+            v: self.range.encode_uv(v),
         }
     }
 }
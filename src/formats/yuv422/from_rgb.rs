@@ -1,5 +1,6 @@
 use super::Yuv422Block;
 use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::formats::yuv_range::YuvRange;
 use crate::formats::yuv422::Yuv422Src;
 use crate::vector::Vector;
 use crate::{ColorInfo, ColorSpace, ColorTransfer, ConvertError};
@@ -8,23 +9,33 @@ use crate::{ColorInfo, ColorSpace, ColorTransfer, ConvertError};
 pub(crate) struct FromRgb<S> {
     rgba_src: S,
     space: ColorSpace,
+    rgb_to_yuv: [[f32; 3]; 3],
     transfer: ColorTransfer,
     rgb_to_xyz: &'static [[f32; 3]; 3],
-    full_range: bool,
+    range: YuvRange,
+    max_value: f32,
 }
 
 impl<S: RgbaSrc> FromRgb<S> {
-    pub(crate) fn new(color: &ColorInfo, rgba_src: S) -> Result<Self, ConvertError> {
+    pub(crate) fn new(
+        color: &ColorInfo,
+        bits_per_component: usize,
+        rgba_src: S,
+    ) -> Result<Self, ConvertError> {
         let ColorInfo::YUV(yuv) = color else {
             return Err(ConvertError::InvalidColorInfo);
         };
 
+        yuv.space.validate(bits_per_component, yuv.full_range)?;
+
         Ok(Self {
             rgba_src,
             space: yuv.space,
+            rgb_to_yuv: yuv.space.rgb_to_yuv_mat(),
             transfer: yuv.transfer,
             rgb_to_xyz: yuv.primaries.rgb_to_xyz_mat(),
-            full_range: yuv.full_range,
+            range: YuvRange::new(bits_per_component, yuv.full_range),
+            max_value: crate::formats::max_value_for_bits(bits_per_component),
         })
     }
 
@@ -34,38 +45,37 @@ impl<S: RgbaSrc> FromRgb<S> {
         px0: RgbaPixel<V>,
         px1: RgbaPixel<V>,
     ) -> ([V; 2], V, V) {
-        let (mut y0, u0, v0) =
-            self.space
-                .rgb_to_yuv(self.transfer, self.rgb_to_xyz, px0.r, px0.g, px0.b);
-
-        let (mut y1, u1, v1) =
-            self.space
-                .rgb_to_yuv(self.transfer, self.rgb_to_xyz, px1.r, px1.g, px1.b);
+        let (y0, u0, v0) = self.space.rgb_to_yuv(
+            &self.rgb_to_yuv,
+            self.transfer,
+            self.rgb_to_xyz,
+            self.max_value,
+            px0.r,
+            px0.g,
+            px0.b,
+        );
+
+        let (y1, u1, v1) = self.space.rgb_to_yuv(
+            &self.rgb_to_yuv,
+            self.transfer,
+            self.rgb_to_xyz,
+            self.max_value,
+            px1.r,
+            px1.g,
+            px1.b,
+        );
 
         let (u0, u1) = u0.unzip(u1);
         let (v0, v1) = v0.unzip(v1);
 
-        let mut u = u0.vadd(u1).vdivf(2.0);
-        let mut v = v0.vadd(v1).vdivf(2.0);
-
-        // U & V scales from -0.5..=0.5, so bring that up into 0..=1
-        u = u.vaddf(0.5);
-        v = v.vaddf(0.5);
-
-        if !self.full_range {
-            let v16 = V::splat(16.0 / 255.0);
-
-            let y_scale = V::splat(219.0 / 255.0);
-            let uv_scale = V::splat(224.0 / 255.0);
-
-            y0 = v16.vadd(y0.vmul(y_scale));
-            y1 = v16.vadd(y1.vmul(y_scale));
-
-            u = v16.vadd(u.vmul(uv_scale));
-            v = v16.vadd(v.vmul(uv_scale));
-        }
+        let u = u0.vadd(u1).vdivf(2.0);
+        let v = v0.vadd(v1).vdivf(2.0);
 
-        ([y0, y1], u, v)
+        (
+            [self.range.encode_y(y0), self.range.encode_y(y1)],
+            self.range.encode_uv(u),
+            self.range.encode_uv(v),
+        )
     }
 }
 
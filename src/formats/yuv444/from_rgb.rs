@@ -1,6 +1,7 @@
 use super::{Yuv444Block, Yuv444Src};
 use crate::color::ColorInfo;
 use crate::formats::rgb::{RgbaPixel, RgbaSrc};
+use crate::formats::yuv_range::YuvRange;
 use crate::formats::yuv444::Yuv444Pixel;
 use crate::vector::Vector;
 use crate::{ColorSpace, ColorTransfer, ConvertError};
@@ -9,49 +10,53 @@ use crate::{ColorSpace, ColorTransfer, ConvertError};
 pub(crate) struct FromRgb<S> {
     rgba_src: S,
     space: ColorSpace,
+    rgb_to_yuv: [[f32; 3]; 3],
     transfer: ColorTransfer,
     rgb_to_xyz: &'static [[f32; 3]; 3],
-    full_range: bool,
+    range: YuvRange,
+    max_value: f32,
 }
 
 impl<S: RgbaSrc> FromRgb<S> {
-    pub(crate) fn new(color: &ColorInfo, rgba_src: S) -> Result<Self, ConvertError> {
+    pub(crate) fn new(
+        color: &ColorInfo,
+        bits_per_component: usize,
+        rgba_src: S,
+    ) -> Result<Self, ConvertError> {
         let ColorInfo::YUV(yuv) = color else {
             return Err(ConvertError::InvalidColorInfo);
         };
 
+        yuv.space.validate(bits_per_component, yuv.full_range)?;
+
         Ok(Self {
             rgba_src,
             space: yuv.space,
+            rgb_to_yuv: yuv.space.rgb_to_yuv_mat(),
             transfer: yuv.transfer,
             rgb_to_xyz: yuv.primaries.rgb_to_xyz_mat(),
-            full_range: yuv.full_range,
+            range: YuvRange::new(bits_per_component, yuv.full_range),
+            max_value: crate::formats::max_value_for_bits(bits_per_component),
         })
     }
 
     #[inline(always)]
     unsafe fn convert_rgb_to_yuv<V: Vector>(&self, px: RgbaPixel<V>) -> Yuv444Pixel<V> {
-        let (mut y, mut u, mut v) =
-            self.space
-                .rgb_to_yuv(self.transfer, self.rgb_to_xyz, px.r, px.g, px.b);
-
-        // U & V scales from -0.5..=0.5, so bring that up into 0..=1
-        u = u.vaddf(0.5);
-        v = v.vaddf(0.5);
-
-        if !self.full_range {
-            let v16 = V::splat(16.0 / 255.0);
+        let (y, u, v) = self.space.rgb_to_yuv(
+            &self.rgb_to_yuv,
+            self.transfer,
+            self.rgb_to_xyz,
+            self.max_value,
+            px.r,
+            px.g,
+            px.b,
+        );
 
-            let y_scale = V::splat(219.0 / 255.0);
-            let uv_scale = V::splat(224.0 / 255.0);
-
-            y = v16.vadd(y.vmul(y_scale));
-
-            u = v16.vadd(u.vmul(uv_scale));
-            v = v16.vadd(v.vmul(uv_scale));
+        Yuv444Pixel {
+            y: self.range.encode_y(y),
+            u: self.range.encode_uv(u),
+            v: self.range.encode_uv(v),
         }
-
-        Yuv444Pixel { y, u, v }
     }
 }
 
@@ -1,6 +1,7 @@
 use super::{Yuv444Block, Yuv444Pixel, Yuv444Src};
 use crate::color::ColorInfo;
 use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::formats::yuv_range::YuvRange;
 use crate::vector::Vector;
 use crate::{ColorSpace, ColorTransfer, ConvertError};
 
@@ -9,58 +10,54 @@ pub(crate) struct ToRgb<S> {
     yuv444_src: S,
 
     space: ColorSpace,
+    yuv_to_rgb: [[f32; 3]; 3],
     transfer: ColorTransfer,
     xyz_to_rgb: &'static [[f32; 3]; 3],
-    full_range: bool,
+    range: YuvRange,
+    max_value: f32,
 }
 
 impl<S: Yuv444Src> ToRgb<S> {
-    pub(crate) fn new(color: &ColorInfo, yuv444_src: S) -> Result<Self, ConvertError> {
+    pub(crate) fn new(
+        color: &ColorInfo,
+        bits_per_component: usize,
+        yuv444_src: S,
+    ) -> Result<Self, ConvertError> {
         let ColorInfo::YUV(yuv) = color else {
             return Err(ConvertError::InvalidColorInfo);
         };
 
+        yuv.space.validate(bits_per_component, yuv.full_range)?;
+
         Ok(Self {
             yuv444_src,
             space: yuv.space,
+            yuv_to_rgb: yuv.space.yuv_to_rgb_mat(),
             transfer: yuv.transfer,
             xyz_to_rgb: yuv.primaries.xyz_to_rgb_mat(),
-            full_range: yuv.full_range,
+            range: YuvRange::new(bits_per_component, yuv.full_range),
+            max_value: crate::formats::max_value_for_bits(bits_per_component),
         })
     }
 
     #[inline(always)]
     unsafe fn convert_yuv_to_rgb<V: Vector>(&self, px: Yuv444Pixel<V>) -> RgbaPixel<V> {
-        let Yuv444Pixel {
-            mut y,
-            mut u,
-            mut v,
-        } = px;
-
-        // If the YUV components don't use the full range of u8 scale them to the full range
-        // Y  16..=235 -> 0..255 Y  = (1.164 * (Y  - 16))
-        // UV 16..=240 -> 0..255 UV = (1.138 * (UV - 16))
-        if !self.full_range {
-            let v16 = V::splat(16.0 / 255.0);
-            let y_scale = V::splat(255.0 / 219.0);
-            let uv_scale = V::splat(255.0 / 224.0);
-
-            y = y.vsub(v16);
-            y = y.vmul(y_scale);
-
-            u = u.vsub(v16);
-            v = v.vsub(v16);
-
-            u = u.vmul(uv_scale);
-            v = v.vmul(uv_scale);
-        }
+        let Yuv444Pixel { y, u, v } = px;
 
-        u = u.vsubf(0.5);
-        v = v.vsubf(0.5);
+        // Bring Y into 0..=1 and U & V into -0.5..=0.5
+        let y = self.range.decode_y(y);
+        let u = self.range.decode_uv(u);
+        let v = self.range.decode_uv(v);
 
-        let (r, g, b) = self
-            .space
-            .yuv_to_rgb(self.transfer, self.xyz_to_rgb, y, u, v);
+        let (r, g, b) = self.space.yuv_to_rgb(
+            &self.yuv_to_rgb,
+            self.transfer,
+            self.xyz_to_rgb,
+            self.max_value,
+            y,
+            u,
+            v,
+        );
 
         RgbaPixel {
             r,
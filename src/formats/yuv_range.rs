@@ -0,0 +1,129 @@
+use crate::vector::Vector;
+
+/// Quantization of normalized YUV values into the code values of a format with a given bit depth
+///
+/// Follows the integer formulas of Rec. ITU-R BT.2100-2 table 9, e.g. limited range 10-bit
+/// maps Y to 64..=940 and U & V to 64..=960 with 512 as achromatic value.
+///
+/// Full range uses the same achromatic value of `2^(N-1)`, so U & V are offset by `2^(N-1) / (2^N - 1)`
+/// (e.g. 128 for 8-bit) instead of the `0.5` (127.5 for 8-bit) used up to version 0.4.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct YuvRange {
+    y_offset: f32,
+    y_scale: f32,
+    uv_offset: f32,
+    uv_scale: f32,
+}
+
+impl YuvRange {
+    pub(crate) fn new(bits_per_component: usize, full_range: bool) -> Self {
+        let max_value = super::max_value_for_bits(bits_per_component);
+
+        // Multiplier to scale the 8-bit code values to the format's bit depth, 2^(n-8)
+        let step = (1u32 << bits_per_component) as f32 / 256.0;
+
+        let uv_offset = (128.0 * step) / max_value;
+
+        if full_range {
+            Self {
+                y_offset: 0.0,
+                y_scale: 1.0,
+                uv_offset,
+                uv_scale: 1.0,
+            }
+        } else {
+            Self {
+                y_offset: (16.0 * step) / max_value,
+                y_scale: (219.0 * step) / max_value,
+                uv_offset,
+                uv_scale: (224.0 * step) / max_value,
+            }
+        }
+    }
+
+    /// Map Y from 0..=1 into the format's range
+    #[inline(always)]
+    pub(crate) unsafe fn encode_y<V: Vector>(&self, y: V) -> V {
+        y.vmulf(self.y_scale).vaddf(self.y_offset)
+    }
+
+    /// Map U or V from -0.5..=0.5 into the format's range
+    #[inline(always)]
+    pub(crate) unsafe fn encode_uv<V: Vector>(&self, uv: V) -> V {
+        uv.vmulf(self.uv_scale).vaddf(self.uv_offset)
+    }
+
+    /// Map Y from the format's range into 0..=1
+    #[inline(always)]
+    pub(crate) unsafe fn decode_y<V: Vector>(&self, y: V) -> V {
+        y.vsubf(self.y_offset).vdivf(self.y_scale)
+    }
+
+    /// Map U or V from the format's range into -0.5..=0.5
+    #[inline(always)]
+    pub(crate) unsafe fn decode_uv<V: Vector>(&self, uv: V) -> V {
+        uv.vsubf(self.uv_offset).vdivf(self.uv_scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::YuvRange;
+    use crate::formats::max_value_for_bits;
+
+    fn code_values(bits: usize, full_range: bool) -> [f32; 4] {
+        let range = YuvRange::new(bits, full_range);
+        let max_value = max_value_for_bits(bits);
+
+        unsafe {
+            [
+                range.encode_y(0.0f32) * max_value,
+                range.encode_y(1.0f32) * max_value,
+                range.encode_uv(-0.5f32) * max_value,
+                range.encode_uv(0.5f32) * max_value,
+            ]
+        }
+        .map(f32::round)
+    }
+
+    #[test]
+    fn limited_range_code_values() {
+        assert_eq!(code_values(8, false), [16.0, 235.0, 16.0, 240.0]);
+        assert_eq!(code_values(10, false), [64.0, 940.0, 64.0, 960.0]);
+        assert_eq!(code_values(12, false), [256.0, 3760.0, 256.0, 3840.0]);
+        assert_eq!(code_values(16, false), [4096.0, 60160.0, 4096.0, 61440.0]);
+    }
+
+    #[test]
+    fn full_range_chroma_offset() {
+        for bits in [8, 10, 12, 16] {
+            let range = YuvRange::new(bits, true);
+            let max_value = max_value_for_bits(bits);
+
+            let offset = unsafe { range.encode_uv(0.0f32) };
+
+            // Previously the normalized offset was 0.5, which isn't a code value
+            assert_ne!(offset, 0.5);
+            assert_eq!(offset, (1u32 << (bits - 1)) as f32 / max_value);
+        }
+
+        let range = YuvRange::new(8, true);
+        assert_eq!(unsafe { range.encode_uv(0.0f32) } * 255.0, 128.0);
+        assert_eq!(unsafe { range.decode_uv(128.0f32 / 255.0) }, 0.0);
+        assert!((unsafe { range.decode_uv(0.5f32) } + 0.5 / 255.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn achromatic_code_values() {
+        for (bits, achromatic) in [(8, 128.0), (10, 512.0), (12, 2048.0)] {
+            for full_range in [false, true] {
+                let range = YuvRange::new(bits, full_range);
+                let max_value = max_value_for_bits(bits);
+
+                let value = unsafe { range.encode_uv(0.0f32) } * max_value;
+
+                assert!((value - achromatic).abs() < 1e-3);
+            }
+        }
+    }
+}
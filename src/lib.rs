@@ -33,17 +33,27 @@ use formats::*;
 mod color;
 mod copy;
 mod crop;
+mod fill;
 mod formats;
 mod image;
 mod image_traits;
+pub mod light_level;
+mod lut;
+pub mod metrics;
 #[cfg(feature = "multi-thread")]
 mod multi_thread;
+mod options;
+mod overlay;
+mod pattern;
 mod pixel_format;
 mod plane_decs;
 mod planes;
 mod primitive;
 #[cfg(feature = "resize")]
 pub mod resize;
+pub mod scopes;
+pub mod statistics;
+mod transform;
 pub(crate) mod util;
 mod vector;
 
@@ -59,16 +69,25 @@ mod arch {
     pub(crate) use std::arch::is_aarch64_feature_detected;
 }
 
-pub use color::{ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, RgbColorInfo, YuvColorInfo};
+pub use color::{
+    Cicp, CicpError, ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, LumaCoefficients,
+    RgbColorInfo, YuvColorInfo, convert_color,
+};
 #[doc(hidden)]
 pub use copy::copy;
 pub use crop::{CropError, Cropped, Window};
+pub use fill::{FillError, clear, fill};
 pub use image::{BufferKind, Image, ImageError};
 pub use image_traits::{ImageMut, ImageRef, ImageRefExt};
+pub use lut::{Lut3D, LutError, LutInterpolation};
 #[cfg(feature = "multi-thread")]
-pub use multi_thread::convert_multi_thread;
+pub use multi_thread::{convert_multi_thread, convert_multi_thread_with_options};
+pub use options::{AlphaBackground, ColorAdjustments, ConvertOptions};
+pub use overlay::{OverlayError, overlay};
+pub use pattern::{TestPattern, fill_test_pattern};
 pub use pixel_format::{BoundsCheckError, PixelFormat};
 pub use planes::*;
+pub use transform::{Transform, transform};
 
 /// Errors that may occur when trying to convert an image
 #[derive(Debug, thiserror::Error)]
@@ -94,9 +113,29 @@ pub enum ConvertError {
 /// The given images (or at least their included window) must have dimensions (width, height) divisible by 2.
 #[inline(never)]
 pub fn convert(src: &dyn ImageRef, dst: &mut dyn ImageMut) -> Result<(), ConvertError> {
+    convert_with_options(src, dst, &ConvertOptions::default())
+}
+
+/// Same as [`convert`] but with additional [`ConvertOptions`]
+#[inline(never)]
+pub fn convert_with_options(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    options: &ConvertOptions,
+) -> Result<(), ConvertError> {
+    convert_rows(src, dst, options, 0)
+}
+
+/// Convert `src` to `dst`, which are rows starting at `y_offset` of a larger image
+pub(crate) fn convert_rows(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    options: &ConvertOptions,
+    y_offset: usize,
+) -> Result<(), ConvertError> {
     verify_input_windows(src, dst)?;
 
-    if src.format() == dst.format() && src.color() == dst.color() {
+    if src.format() == dst.format() && src.color() == dst.color() && !options.modifies_colors() {
         // No color or pixel conversion needed just copy it over
         return copy(src, dst);
     }
@@ -104,10 +143,35 @@ pub fn convert(src: &dyn ImageRef, dst: &mut dyn ImageMut) -> Result<(), Convert
     let src_color = src.color();
     let dst_color = dst.color();
 
-    let reader = read_any_to_rgba(src)?;
+    let mut reader = read_any_to_rgba(src)?;
 
-    if need_transfer_and_primaries_convert(&src_color, &dst_color) {
-        let reader = TransferAndPrimariesConvert::new(&src_color, &dst_color, reader);
+    if let Some(background) = options.alpha_background
+        && src.format().has_alpha()
+        && !dst.format().has_alpha()
+    {
+        reader = Box::new(AlphaComposite::new(
+            &src_color, background, options, y_offset, reader,
+        ));
+    }
+
+    if options.modifies_colors() {
+        if need_transfer_and_primaries_convert(&src_color, &dst_color) {
+            reader = Box::new(TransferAndPrimariesConvert::new(
+                &src_color, &dst_color, options, reader,
+            ));
+        }
+
+        if let Some(adjustments) = &options.color_adjustments {
+            reader = Box::new(ColorAdjust::new(adjustments, &dst_color, reader));
+        }
+
+        if let Some(lut) = &options.lut {
+            reader = Box::new(ApplyLut::new(lut, &dst_color, options, reader));
+        }
+
+        rgba_to_any(dst, reader)
+    } else if need_transfer_and_primaries_convert(&src_color, &dst_color) {
+        let reader = TransferAndPrimariesConvert::new(&src_color, &dst_color, options, reader);
 
         rgba_to_any(dst, reader)
     } else {
@@ -125,66 +189,79 @@ fn read_any_to_rgba<'a>(
         #[cfg(feature = "I420")]
         I420 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read3Plane::<u8>::new(src)?,
         )?)),
         #[cfg(feature = "I422")]
         I422 => Ok(Box::new(yuv422::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv422::Read3Plane::<u8>::new(src)?,
         )?)),
         #[cfg(feature = "I444")]
         I444 => Ok(Box::new(yuv444::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv444::Read3Plane::<u8>::new(src)?,
         )?)),
         #[cfg(feature = "I010")]
         I010 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "I012")]
         I012 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "I210")]
         I210 => Ok(Box::new(yuv422::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv422::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "I212")]
         I212 => Ok(Box::new(yuv422::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv422::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "I410")]
         I410 => Ok(Box::new(yuv444::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv444::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "I412")]
         I412 => Ok(Box::new(yuv444::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv444::Read3Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "NV12")]
         NV12 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read2Plane::<u8>::new(src)?,
         )?)),
         #[cfg(feature = "P010")]
         P010 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read2Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "P012")]
         P012 => Ok(Box::new(yuv420::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv420::Read2Plane::<u16>::new(src)?,
         )?)),
         #[cfg(feature = "YUYV")]
         YUYV => Ok(Box::new(yuv422::ToRgb::new(
             &src.color(),
+            src.format().bits_per_component(),
             yuv422::Read1Plane::<u8>::new(src)?,
         )?)),
         #[cfg(feature = "RGBA")]
@@ -207,34 +284,61 @@ fn rgba_to_any(dst: &mut dyn ImageMut, reader: impl rgb::RgbaSrc) -> Result<(),
     use PixelFormat::*;
 
     let color = dst.color();
+    let bits = dst.format().bits_per_component();
 
     match dst.format() {
         #[cfg(feature = "I420")]
-        I420 => yuv420::Write3Plane::<u8, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        I420 => {
+            yuv420::Write3Plane::<u8, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I422")]
-        I422 => yuv422::Write3Plane::<u8, _>::write(dst, yuv422::FromRgb::new(&color, reader)?),
+        I422 => {
+            yuv422::Write3Plane::<u8, _>::write(dst, yuv422::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I444")]
-        I444 => yuv444::Write3Plane::<u8, _>::write(dst, yuv444::FromRgb::new(&color, reader)?),
+        I444 => {
+            yuv444::Write3Plane::<u8, _>::write(dst, yuv444::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I010")]
-        I010 => yuv420::Write3Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        I010 => {
+            yuv420::Write3Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I012")]
-        I012 => yuv420::Write3Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        I012 => {
+            yuv420::Write3Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I210")]
-        I210 => yuv422::Write3Plane::<u16, _>::write(dst, yuv422::FromRgb::new(&color, reader)?),
+        I210 => {
+            yuv422::Write3Plane::<u16, _>::write(dst, yuv422::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I212")]
-        I212 => yuv422::Write3Plane::<u16, _>::write(dst, yuv422::FromRgb::new(&color, reader)?),
+        I212 => {
+            yuv422::Write3Plane::<u16, _>::write(dst, yuv422::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I410")]
-        I410 => yuv444::Write3Plane::<u16, _>::write(dst, yuv444::FromRgb::new(&color, reader)?),
+        I410 => {
+            yuv444::Write3Plane::<u16, _>::write(dst, yuv444::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "I412")]
-        I412 => yuv444::Write3Plane::<u16, _>::write(dst, yuv444::FromRgb::new(&color, reader)?),
+        I412 => {
+            yuv444::Write3Plane::<u16, _>::write(dst, yuv444::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "NV12")]
-        NV12 => yuv420::Write2Plane::<u8, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        NV12 => {
+            yuv420::Write2Plane::<u8, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "P010")]
-        P010 => yuv420::Write2Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        P010 => {
+            yuv420::Write2Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "P012")]
-        P012 => yuv420::Write2Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, reader)?),
+        P012 => {
+            yuv420::Write2Plane::<u16, _>::write(dst, yuv420::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "YUYV")]
-        YUYV => yuv422::Write1Plane::<u8, _>::write(dst, yuv422::FromRgb::new(&color, reader)?),
+        YUYV => {
+            yuv422::Write1Plane::<u8, _>::write(dst, yuv422::FromRgb::new(&color, bits, reader)?)
+        }
         #[cfg(feature = "RGBA")]
         RGBA => rgb::WriteRgba::<u8, _>::write(dst, reader),
         #[cfg(feature = "BGRA")]
@@ -0,0 +1,168 @@
+//! Content light level analysis of HDR images, as required by the HDR10 metadata (CTA-861.3)
+
+use crate::formats::DynRgbaReader;
+use crate::formats::rgb::RgbaSrc;
+use crate::formats::visit_2x2::{Image2x2Visitor, visit};
+use crate::vector::Vector;
+use crate::{ColorTransfer, ConvertError, ImageRef, read_any_to_rgba};
+
+/// Nominal peak luminance of the display HLG content is assumed to be shown on, in cd/m²
+pub const HLG_NOMINAL_PEAK_LUMINANCE: f32 = 1000.0;
+
+/// Errors that may occur when analyzing the content light level
+#[derive(Debug, thiserror::Error)]
+pub enum LightLevelError {
+    #[error("transfer {0:?} is neither PQ nor HLG")]
+    UnsupportedTransfer(ColorTransfer),
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+}
+
+/// Content light levels in cd/m² (nits)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ContentLightLevel {
+    /// Maximum content light level, the largest R, G or B component of any pixel
+    pub max_cll: f32,
+    /// Maximum frame-average light level, the largest average over all pixels of their largest R, G or B component
+    pub max_fall: f32,
+}
+
+/// Compute the content light level of a single frame with a PQ or HLG transfer function
+///
+/// HLG content is converted to display light for a display with a peak luminance of
+/// [`HLG_NOMINAL_PEAK_LUMINANCE`]. The image must have dimensions divisible by 2.
+pub fn content_light_level(image: &dyn ImageRef) -> Result<ContentLightLevel, LightLevelError> {
+    let color = image.color();
+    let transfer = color.transfer();
+
+    if !matches!(transfer, ColorTransfer::BT2100PQ | ColorTransfer::BT2100HLG) {
+        return Err(LightLevelError::UnsupportedTransfer(transfer));
+    }
+
+    if image.width() % 2 == 1 || image.height() % 2 == 1 {
+        return Err(ConvertError::OddImageDimensions.into());
+    }
+
+    let m = color.primaries().rgb_to_xyz_mat();
+
+    let mut max = 0.0f32;
+    let mut sum = 0.0f64;
+
+    visit(
+        image.width(),
+        image.height(),
+        LightLevelVisitor {
+            reader: read_any_to_rgba(image)?,
+            transfer,
+            luminance_weights: [m[0][1], m[1][1], m[2][1]],
+            max: &mut max,
+            sum: &mut sum,
+        },
+    );
+
+    let pixels = (image.width() * image.height()).max(1);
+
+    Ok(ContentLightLevel {
+        max_cll: max,
+        max_fall: (sum / pixels as f64) as f32,
+    })
+}
+
+/// Accumulates the content light level over multiple frames
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ContentLightLevelAccumulator {
+    level: ContentLightLevel,
+    frames: u64,
+}
+
+impl ContentLightLevelAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Analyze a frame and add it to the accumulated light level, returns the light level of the frame
+    pub fn add_frame(
+        &mut self,
+        image: &dyn ImageRef,
+    ) -> Result<ContentLightLevel, LightLevelError> {
+        let level = content_light_level(image)?;
+
+        self.add(level);
+
+        Ok(level)
+    }
+
+    /// Add the already computed light level of a frame
+    pub fn add(&mut self, level: ContentLightLevel) {
+        self.level.max_cll = self.level.max_cll.max(level.max_cll);
+        self.level.max_fall = self.level.max_fall.max(level.max_fall);
+        self.frames += 1;
+    }
+
+    /// Number of frames added
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Content light level of all frames added so far
+    pub fn content_light_level(&self) -> ContentLightLevel {
+        self.level
+    }
+}
+
+struct LightLevelVisitor<'a, 'b> {
+    reader: Box<dyn DynRgbaReader + 'a>,
+    transfer: ColorTransfer,
+    luminance_weights: [f32; 3],
+
+    max: &'b mut f32,
+    sum: &'b mut f64,
+}
+
+impl Image2x2Visitor for LightLevelVisitor<'_, '_> {
+    #[inline(always)]
+    unsafe fn visit<V: Vector>(&mut self, x: usize, y: usize) {
+        let mut block = self.reader.read::<V>(x, y);
+
+        self.transfer.scaled_to_linear_v(&mut [
+            &mut block.px00.r,
+            &mut block.px00.g,
+            &mut block.px00.b,
+            &mut block.px01.r,
+            &mut block.px01.g,
+            &mut block.px01.b,
+            &mut block.px10.r,
+            &mut block.px10.g,
+            &mut block.px10.b,
+            &mut block.px11.r,
+            &mut block.px11.g,
+            &mut block.px11.b,
+        ]);
+
+        let [wr, wg, wb] = self.luminance_weights;
+
+        for px in [block.px00, block.px01, block.px10, block.px11] {
+            let mut light = px.r.vmax(px.g).vmax(px.b);
+
+            if self.transfer == ColorTransfer::BT2100HLG {
+                // HLG OOTF, scene light to display light with a system gamma of 1.2
+                let luminance = px.r.vmulf(wr).vadd(px.g.vmulf(wg)).vadd(px.b.vmulf(wb));
+                let gain = luminance
+                    .vmaxf(1e-6)
+                    .vpowf(1.2 - 1.0)
+                    .vmulf(HLG_NOMINAL_PEAK_LUMINANCE);
+
+                light = light.vmul(gain);
+            }
+
+            let mut values = [0.0f32; 16];
+            light.store(values.as_mut_ptr());
+
+            for value in &values[..V::LEN] {
+                *self.max = self.max.max(*value);
+                *self.sum += f64::from(*value);
+            }
+        }
+    }
+}
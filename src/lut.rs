@@ -0,0 +1,389 @@
+use crate::vector::Vector;
+
+/// Errors that may occur when creating a [`Lut3D`]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LutError {
+    #[error("LUT size must be between 2 and {max}, got {0}", max = Lut3D::MAX_SIZE)]
+    InvalidSize(usize),
+
+    #[error("expected {expected} LUT entries, got {got}")]
+    InvalidNumberOfEntries { expected: usize, got: usize },
+
+    #[error("LUT domain minimum must be smaller than its maximum")]
+    InvalidDomain,
+
+    #[error("1D LUTs are not supported")]
+    Unsupported1D,
+
+    #[error("missing LUT_3D_SIZE keyword")]
+    MissingSize,
+
+    #[error("failed to parse line {0}")]
+    InvalidLine(usize),
+}
+
+/// Interpolation used to compute values between the entries of a [`Lut3D`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LutInterpolation {
+    /// Interpolate between the 8 surrounding entries
+    Trilinear,
+    /// Interpolate between the 4 entries of the surrounding tetrahedron, preserves the neutral axis better and is
+    /// cheaper than trilinear interpolation
+    #[default]
+    Tetrahedral,
+}
+
+/// 3D lookup table mapping RGB values to RGB values
+///
+/// Can be applied during conversion using [`ConvertOptions::lut`](crate::ConvertOptions::lut).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3D {
+    size: usize,
+    /// Interleaved RGB entries, with red changing the fastest and blue the slowest
+    table: Vec<f32>,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+}
+
+impl Lut3D {
+    /// Largest supported number of entries per axis, all table indices must be exactly representable as f32
+    pub const MAX_SIZE: usize = 177;
+
+    /// Create a LUT with `size` entries per axis from `size³` entries, with red changing the fastest and blue the
+    /// slowest (same order as `.cube` files).
+    ///
+    /// The domain of the LUT is `0.0..=1.0` for all components.
+    pub fn new(size: usize, entries: Vec<[f32; 3]>) -> Result<Self, LutError> {
+        if !(2..=Self::MAX_SIZE).contains(&size) {
+            return Err(LutError::InvalidSize(size));
+        }
+
+        let expected = size * size * size;
+
+        if entries.len() != expected {
+            return Err(LutError::InvalidNumberOfEntries {
+                expected,
+                got: entries.len(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            table: entries.into_iter().flatten().collect(),
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+        })
+    }
+
+    /// Set the input range of the LUT, values are clamped to it before being looked up
+    pub fn with_domain(mut self, min: [f32; 3], max: [f32; 3]) -> Result<Self, LutError> {
+        // Written to also reject NaN
+        if !min.iter().zip(&max).all(|(min, max)| min < max) {
+            return Err(LutError::InvalidDomain);
+        }
+
+        self.domain_min = min;
+        self.domain_max = max;
+
+        Ok(self)
+    }
+
+    /// Parse the contents of an Adobe/Resolve `.cube` file
+    ///
+    /// Supports the `LUT_3D_SIZE`, `DOMAIN_MIN`, `DOMAIN_MAX` and `LUT_3D_INPUT_RANGE` keywords. Lines starting
+    /// with any other keyword, e.g. `LUT_IN_VIDEO_RANGE` or `LUT_OUT_VIDEO_RANGE` written by Resolve, are ignored.
+    pub fn parse_cube(cube: &str) -> Result<Self, LutError> {
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut entries = Vec::new();
+
+        for (i, line) in cube.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+
+            let floats = |words: std::str::SplitWhitespace<'_>| -> Result<Vec<f32>, LutError> {
+                words
+                    .map(|w| w.parse().map_err(|_| LutError::InvalidLine(line_number)))
+                    .collect()
+            };
+
+            let triple = |values: Vec<f32>| -> Result<[f32; 3], LutError> {
+                values
+                    .try_into()
+                    .map_err(|_| LutError::InvalidLine(line_number))
+            };
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" | "LUT_1D_INPUT_RANGE" => return Err(LutError::Unsupported1D),
+                "LUT_3D_SIZE" => {
+                    let value = words
+                        .next()
+                        .and_then(|w| w.parse().ok())
+                        .ok_or(LutError::InvalidLine(line_number))?;
+
+                    size = Some(value);
+                }
+                "DOMAIN_MIN" => domain_min = triple(floats(words)?)?,
+                "DOMAIN_MAX" => domain_max = triple(floats(words)?)?,
+                "LUT_3D_INPUT_RANGE" => {
+                    let [min, max] = floats(words)?
+                        .try_into()
+                        .map_err(|_| LutError::InvalidLine(line_number))?;
+
+                    domain_min = [min; 3];
+                    domain_max = [max; 3];
+                }
+                _ if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => {
+                    let values = floats(line.split_whitespace())?;
+
+                    entries.push(triple(values)?);
+                }
+            }
+        }
+
+        let size = size.ok_or(LutError::MissingSize)?;
+
+        Self::new(size, entries)?.with_domain(domain_min, domain_max)
+    }
+
+    /// Number of entries per axis
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Apply the LUT to a single RGB value
+    pub fn apply(&self, interpolation: LutInterpolation, rgb: [f32; 3]) -> [f32; 3] {
+        let [r, g, b] = rgb;
+
+        // Safety: f32 is not a SIMD type
+        let (r, g, b) = unsafe { self.apply_v(interpolation, r, g, b) };
+
+        [r, g, b]
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn apply_v<V: Vector>(
+        &self,
+        interpolation: LutInterpolation,
+        r: V,
+        g: V,
+        b: V,
+    ) -> (V, V, V) {
+        let max_index = (self.size - 1) as f32;
+
+        // Position inside the LUT, split into the index of the lower entry and the distance to it.
+        // vmax comes first so that NaNs are replaced with 0 before being used as index.
+        let coordinate = |v: V, c: usize| {
+            let v = v
+                .vsubf(self.domain_min[c])
+                .vmulf(max_index / (self.domain_max[c] - self.domain_min[c]))
+                .vmaxf(0.0)
+                .vminf(max_index);
+
+            let index = v.vfloor().vminf(max_index - 1.0);
+
+            (index, v.vsub(index))
+        };
+
+        let (ri, rf) = coordinate(r, 0);
+        let (gi, gf) = coordinate(g, 1);
+        let (bi, bf) = coordinate(b, 2);
+
+        // Offsets between neighboring entries along every axis
+        let r_step = 3.0;
+        let g_step = r_step * self.size as f32;
+        let b_step = g_step * self.size as f32;
+
+        let base = ri
+            .vmulf(r_step)
+            .vadd(gi.vmulf(g_step))
+            .vadd(bi.vmulf(b_step));
+
+        let fetch = |offset: V| {
+            let indices = base.vadd(offset);
+            let table = self.table.as_ptr();
+
+            [
+                V::gather(table, indices),
+                V::gather(table.add(1), indices),
+                V::gather(table.add(2), indices),
+            ]
+        };
+
+        let lerp =
+            |a: [V; 3], b: [V; 3], t: V| [0, 1, 2].map(|i| b[i].vsub(a[i]).vmul(t).vadd(a[i]));
+
+        let [r, g, b] = match interpolation {
+            LutInterpolation::Trilinear => {
+                let c000 = fetch(V::splat(0.0));
+                let c100 = fetch(V::splat(r_step));
+                let c010 = fetch(V::splat(g_step));
+                let c110 = fetch(V::splat(r_step + g_step));
+                let c001 = fetch(V::splat(b_step));
+                let c101 = fetch(V::splat(r_step + b_step));
+                let c011 = fetch(V::splat(g_step + b_step));
+                let c111 = fetch(V::splat(r_step + g_step + b_step));
+
+                let c00 = lerp(c000, c100, rf);
+                let c10 = lerp(c010, c110, rf);
+                let c01 = lerp(c001, c101, rf);
+                let c11 = lerp(c011, c111, rf);
+
+                let c0 = lerp(c00, c10, gf);
+                let c1 = lerp(c01, c11, gf);
+
+                lerp(c0, c1, bf)
+            }
+            LutInterpolation::Tetrahedral => {
+                // The tetrahedron is spanned by c000, c111 and the two entries reached by stepping along the axis
+                // with the largest fraction and then along the axis with the second largest fraction
+                let r_step_v = V::splat(r_step);
+                let g_step_v = V::splat(g_step);
+                let b_step_v = V::splat(b_step);
+
+                let max_step = V::select(
+                    r_step_v,
+                    V::select(g_step_v, b_step_v, bf.le(gf)),
+                    gf.vmax(bf).le(rf),
+                );
+
+                let min_step = V::select(
+                    r_step_v,
+                    V::select(g_step_v, b_step_v, gf.le(bf)),
+                    rf.le(gf.vmin(bf)),
+                );
+
+                let max = rf.vmax(gf).vmax(bf);
+                let min = rf.vmin(gf).vmin(bf);
+                let mid = rf.vadd(gf).vadd(bf).vsub(max).vsub(min);
+
+                let c0 = fetch(V::splat(0.0));
+                let c1 = fetch(max_step);
+                let c2 = fetch(V::splat(r_step + g_step + b_step).vsub(min_step));
+                let c3 = fetch(V::splat(r_step + g_step + b_step));
+
+                let w0 = V::splat(1.0).vsub(max);
+                let w1 = max.vsub(mid);
+                let w2 = mid.vsub(min);
+                let w3 = min;
+
+                [0, 1, 2].map(|i| {
+                    c0[i]
+                        .vmul(w0)
+                        .vadd(c1[i].vmul(w1))
+                        .vadd(c2[i].vmul(w2))
+                        .vadd(c3[i].vmul(w3))
+                })
+            }
+        };
+
+        (r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lut3D, LutError, LutInterpolation};
+
+    const CUBE: &str = "# Swaps red and blue
+TITLE \"swap\"
+LUT_3D_SIZE 2
+
+0 0 0
+0 0 1
+0 1 0
+0 1 1
+1 0 0
+1 0 1
+1 1 0
+1 1 1
+";
+
+    #[test]
+    fn parse_and_apply_cube() {
+        let lut = Lut3D::parse_cube(CUBE).unwrap();
+
+        assert_eq!(lut.size(), 2);
+
+        for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+            let [r, g, b] = lut.apply(interpolation, [0.2, 0.5, 0.9]);
+
+            assert!((r - 0.9).abs() < 1e-6);
+            assert!((g - 0.5).abs() < 1e-6);
+            assert!((b - 0.2).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn parse_cube_skips_unknown_keywords() {
+        let cube = CUBE.replace(
+            "LUT_3D_SIZE 2\n",
+            "LUT_IN_VIDEO_RANGE\nLUT_3D_SIZE 2\nLUT_OUT_VIDEO_RANGE\nLUT_3D_VENDOR_KEY 1 2 3\n",
+        );
+
+        assert_eq!(Lut3D::parse_cube(&cube), Lut3D::parse_cube(CUBE));
+        assert!(Lut3D::parse_cube(&cube).is_ok());
+    }
+
+    #[test]
+    fn tetrahedral_matches_trilinear_on_identity() {
+        let size = 17;
+        let max = (size - 1) as f32;
+
+        let mut entries = vec![];
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    entries.push([r as f32 / max, g as f32 / max, b as f32 / max]);
+                }
+            }
+        }
+
+        let lut = Lut3D::new(size, entries).unwrap();
+
+        for rgb in [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.3, 0.7, 0.1],
+            [0.95, 0.2, 0.55],
+        ] {
+            for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+                let out = lut.apply(interpolation, rgb);
+
+                for (a, b) in out.iter().zip(rgb) {
+                    assert!((a - b).abs() < 1e-5, "{interpolation:?} {rgb:?} {out:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_cube() {
+        assert_eq!(
+            Lut3D::parse_cube("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(LutError::InvalidNumberOfEntries {
+                expected: 8,
+                got: 1
+            })
+        );
+        assert_eq!(Lut3D::parse_cube("0 0 0\n"), Err(LutError::MissingSize));
+        assert_eq!(
+            Lut3D::parse_cube("LUT_1D_SIZE 2\n"),
+            Err(LutError::Unsupported1D)
+        );
+        assert_eq!(
+            Lut3D::parse_cube("LUT_3D_SIZE 2\n0 0\n"),
+            Err(LutError::InvalidLine(2))
+        );
+    }
+}
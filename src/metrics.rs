@@ -0,0 +1,687 @@
+//! Quality metrics comparing a distorted image to a reference image
+
+use crate::color::convert_color::encode;
+use crate::color::primaries::{rgb_to_xyz, xyz_to_rgb};
+use crate::formats::max_value_for_bits;
+use crate::formats::rgb::RgbaSrc;
+use crate::plane_decs::plane_layouts;
+use crate::statistics::Channel;
+use crate::{
+    ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, ConvertError, ConvertOptions, ImageRef,
+    ImageRefExt, PixelFormat, TransferAndPrimariesConvert, need_transfer_and_primaries_convert,
+    read_any_to_rgba,
+};
+
+/// Metrics of a distorted image compared to its reference, returned by [`compare`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// Metrics of every component in the reference's format, Y, U & V for YUV and R, G & B for RGB formats, followed
+    /// by alpha if the format has an alpha channel
+    pub planes: Vec<PlaneMetrics>,
+
+    /// CIEDE2000 color difference, only computed when the reference does not use a PQ or HLG transfer function
+    pub ciede2000: Option<ColorDifference>,
+
+    /// ΔE ITP color difference (Rec. ITU-R BT.2124), only computed when the reference uses a PQ or HLG transfer
+    /// function
+    pub delta_e_itp: Option<ColorDifference>,
+}
+
+/// Metrics of a single component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaneMetrics {
+    /// Peak signal-to-noise ratio in dB, infinite if the component is identical
+    pub psnr: f64,
+
+    /// Mean structural similarity, `None` if the plane is smaller than the 11x11 SSIM window
+    pub ssim: Option<f64>,
+
+    /// Multi-scale structural similarity over 5 scales, `None` if the plane is smaller than 176x176
+    pub ms_ssim: Option<f64>,
+}
+
+/// Per pixel color difference
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorDifference {
+    /// Mean difference over all pixels
+    pub mean: f64,
+    /// Largest difference of a single pixel
+    pub max: f64,
+}
+
+/// Compare `distorted` to `reference`, the images may have any pixel format & color but must have the same size.
+///
+/// The planes are compared using the code values of the reference's format, so PSNR uses the format's maximum code
+/// value (e.g. 255 or 1023) as peak. If `distorted` has the same format & color as `reference` the code values of
+/// both images are read directly. Otherwise `distorted` is converted to the color of `reference` and both images are
+/// quantized to the code values of the reference's format, rounded and clamped to the format's range. Subsampled
+/// chroma planes are compared at the resolution of the reference's chroma planes.
+///
+/// ΔE ITP assumes HLG content to be shown on a 1000 cd/m² display.
+pub fn compare(
+    reference: &dyn ImageRef,
+    distorted: &dyn ImageRef,
+) -> Result<Metrics, ConvertError> {
+    if reference.width() != distorted.width() || reference.height() != distorted.height() {
+        return Err(ConvertError::MismatchedImageSize);
+    }
+
+    if reference.width() % 2 == 1 || reference.height() % 2 == 1 {
+        return Err(ConvertError::OddImageDimensions);
+    }
+
+    reference.bounds_check()?;
+    distorted.bounds_check()?;
+
+    let color = reference.color();
+    let format = reference.format();
+
+    let reference_pixels = read_pixels(reference, &color)?;
+    let distorted_pixels = read_pixels(distorted, &color)?;
+
+    let bits = format.bits_per_component();
+    let peak = f64::from(max_value_for_bits(bits));
+    let (width, height) = (reference.width(), reference.height());
+
+    let (reference_planes, distorted_planes) =
+        if distorted.format() == format && distorted.color() == color {
+            (native_planes(reference), native_planes(distorted))
+        } else {
+            (
+                to_planes(&reference_pixels, width, height, format, &color)?,
+                to_planes(&distorted_pixels, width, height, format, &color)?,
+            )
+        };
+
+    let planes = reference_planes
+        .iter()
+        .zip(&distorted_planes)
+        .map(|(reference, distorted)| PlaneMetrics {
+            psnr: psnr(reference, distorted, peak),
+            ssim: ssim(reference, distorted, peak).map(|(ssim, _)| ssim),
+            ms_ssim: ms_ssim(reference, distorted, peak),
+        })
+        .collect();
+
+    let hdr = matches!(
+        color.transfer(),
+        ColorTransfer::BT2100PQ | ColorTransfer::BT2100HLG
+    );
+
+    let reference_xyz = reference_pixels.iter().map(|px| to_xyz(&color, px));
+    let distorted_xyz = distorted_pixels.iter().map(|px| to_xyz(&color, px));
+    let pairs = reference_xyz.zip(distorted_xyz);
+
+    let (ciede2000, delta_e_itp) = if hdr {
+        let delta_e = pairs.map(|(reference, distorted)| {
+            let reference = to_itp(&color, reference);
+            let distorted = to_itp(&color, distorted);
+
+            720.0 * distance(reference, distorted)
+        });
+
+        (None, Some(ColorDifference::from_iter(delta_e)))
+    } else {
+        let white = white_point(color.primaries());
+
+        let delta_e = pairs.map(|(reference, distorted)| {
+            ciede2000(to_lab(reference, white), to_lab(distorted, white))
+        });
+
+        (Some(ColorDifference::from_iter(delta_e)), None)
+    };
+
+    Ok(Metrics {
+        planes,
+        ciede2000,
+        delta_e_itp,
+    })
+}
+
+impl FromIterator<f64> for ColorDifference {
+    fn from_iter<T: IntoIterator<Item = f64>>(iter: T) -> Self {
+        let (sum, max, n) = iter
+            .into_iter()
+            .fold((0.0, 0.0f64, 0usize), |(sum, max, n), v| {
+                (sum + v, max.max(v), n + 1)
+            });
+
+        Self {
+            mean: sum / n as f64,
+            max,
+        }
+    }
+}
+
+/// Read all pixels of `image` as normalized RGBA in `color`
+fn read_pixels(image: &dyn ImageRef, color: &ColorInfo) -> Result<Vec<[f32; 4]>, ConvertError> {
+    let (width, height) = (image.width(), image.height());
+
+    let mut reader = read_any_to_rgba(image)?;
+
+    if need_transfer_and_primaries_convert(&image.color(), color) {
+        reader = Box::new(TransferAndPrimariesConvert::new(
+            &image.color(),
+            color,
+            &ConvertOptions::default(),
+            reader,
+        ));
+    }
+
+    let mut pixels = vec![[0.0; 4]; width * height];
+
+    for y in (0..height).step_by(2) {
+        for x in (0..width).step_by(2) {
+            // Safety: f32 is not a SIMD type
+            let block = unsafe { reader.read::<f32>(x, y) };
+
+            for (dx, dy, px) in [
+                (0, 0, block.px00),
+                (1, 0, block.px01),
+                (0, 1, block.px10),
+                (1, 1, block.px11),
+            ] {
+                pixels[(y + dy) * width + x + dx] = [px.r, px.g, px.b, px.a];
+            }
+        }
+    }
+
+    Ok(pixels)
+}
+
+#[derive(Debug, Clone)]
+struct Plane {
+    width: usize,
+    height: usize,
+    data: Vec<f64>,
+}
+
+impl Plane {
+    /// Average every `x` * `y` block
+    fn downsample(&self, x: usize, y: usize) -> Self {
+        let width = self.width / x;
+        let height = self.height / y;
+
+        let mut data = Vec::with_capacity(width * height);
+
+        for row in 0..height {
+            for column in 0..width {
+                let sum: f64 = (0..y)
+                    .flat_map(|dy| (0..x).map(move |dx| (row * y + dy, column * x + dx)))
+                    .map(|(row, column)| self.data[row * self.width + column])
+                    .sum();
+
+                data.push(sum / (x * y) as f64);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+/// Read the code values of every channel of `image`, in the order Y, U, V or R, G, B, A
+fn native_planes(image: &dyn ImageRef) -> Vec<Plane> {
+    let format = image.format();
+
+    // Channel, number of rows and the samples of every plane
+    let mut channels: Vec<(Channel, usize, Vec<f64>)> = vec![];
+
+    for (((plane, stride), desc), layout) in image
+        .planes()
+        .zip(format.plane_desc())
+        .zip(plane_layouts(format))
+    {
+        let rows = desc.height_op.op(image.height());
+        let group_len = layout.primitives.len();
+
+        for &(channel, ..) in layout.primitives {
+            if !channels.iter().any(|(c, ..)| *c == channel) {
+                channels.push((channel, rows, vec![]));
+            }
+        }
+
+        for row in 0..rows {
+            let row = &plane[row * stride..];
+
+            for group in 0..image.width() / layout.width {
+                for (i, &(channel, ..)) in layout.primitives.iter().enumerate() {
+                    let index = group * group_len + i;
+
+                    let value = match desc.bytes_per_primitive {
+                        1 => u16::from(row[index]),
+                        _ => u16::from_ne_bytes([row[index * 2], row[index * 2 + 1]]),
+                    };
+
+                    let (.., data) = channels
+                        .iter_mut()
+                        .find(|(c, ..)| *c == channel)
+                        .expect("channel is listed");
+
+                    data.push(f64::from(value));
+                }
+            }
+        }
+    }
+
+    channels.sort_by_key(|(channel, ..)| *channel);
+
+    channels
+        .into_iter()
+        .map(|(_, height, data)| Plane {
+            width: data.len() / height,
+            height,
+            data,
+        })
+        .collect()
+}
+
+/// Split normalized RGBA pixels into planes of code values in the given format & color
+fn to_planes(
+    pixels: &[[f32; 4]],
+    width: usize,
+    height: usize,
+    format: PixelFormat,
+    color: &ColorInfo,
+) -> Result<Vec<Plane>, ConvertError> {
+    let bits = format.bits_per_component();
+    let max_value = f64::from(max_value_for_bits(bits));
+
+    let mut planes: Vec<Vec<f64>> = (0..3).map(|_| Vec::with_capacity(pixels.len())).collect();
+
+    for &[r, g, b, _] in pixels {
+        // Safety: f32 is not a SIMD type
+        let values = unsafe { encode(color, bits, [r, g, b])? };
+
+        for (plane, value) in planes.iter_mut().zip(values) {
+            plane.push(f64::from(value));
+        }
+    }
+
+    if format.has_alpha() {
+        planes.push(
+            pixels
+                .iter()
+                .map(|px| f64::from(px[3]) * max_value)
+                .collect(),
+        );
+    }
+
+    let mut planes: Vec<Plane> = planes
+        .into_iter()
+        .map(|data| Plane {
+            width,
+            height,
+            data,
+        })
+        .collect();
+
+    if let ColorInfo::YUV(..) = color {
+        let (x, y) = format.subsampling();
+
+        for plane in &mut planes[1..3] {
+            *plane = plane.downsample(x, y);
+        }
+    }
+
+    // Cg & Co of YCoCg-R use one bit more than Y
+    let ycocg_r = matches!(color, ColorInfo::YUV(yuv) if yuv.space == ColorSpace::YCoCgR);
+
+    for (i, plane) in planes.iter_mut().enumerate() {
+        let max_value = if ycocg_r && (1..3).contains(&i) {
+            max_value * 2.0 + 1.0
+        } else {
+            max_value
+        };
+
+        for value in &mut plane.data {
+            *value = value.round().clamp(0.0, max_value);
+        }
+    }
+
+    Ok(planes)
+}
+
+fn psnr(reference: &Plane, distorted: &Plane, peak: f64) -> f64 {
+    let squared_error: f64 = reference
+        .data
+        .iter()
+        .zip(&distorted.data)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum();
+
+    let mse = squared_error / reference.data.len() as f64;
+
+    10.0 * (peak * peak / mse).log10()
+}
+
+const SSIM_WINDOW: usize = 11;
+
+/// Normalized gaussian window with a standard deviation of 1.5
+fn ssim_kernel() -> [f64; SSIM_WINDOW] {
+    let center = (SSIM_WINDOW / 2) as f64;
+    let kernel = std::array::from_fn(|i| (-(i as f64 - center).powi(2) / (2.0 * 1.5 * 1.5)).exp());
+    let sum: f64 = kernel.iter().sum();
+
+    kernel.map(|k| k / sum)
+}
+
+/// Filter the values returned by `value(x, y)` with the SSIM window, only keeping positions where the window is
+/// fully inside the plane
+fn ssim_filter(width: usize, height: usize, value: impl Fn(usize) -> f64) -> Vec<f64> {
+    let kernel = ssim_kernel();
+
+    let out_width = width - SSIM_WINDOW + 1;
+    let out_height = height - SSIM_WINDOW + 1;
+
+    let mut horizontal: Vec<f64> = Vec::with_capacity(out_width * height);
+
+    for y in 0..height {
+        for x in 0..out_width {
+            let sum = kernel
+                .iter()
+                .enumerate()
+                .map(|(i, k)| k * value(y * width + x + i))
+                .sum();
+
+            horizontal.push(sum);
+        }
+    }
+
+    let mut out = Vec::with_capacity(out_width * out_height);
+
+    for y in 0..out_height {
+        for x in 0..out_width {
+            let sum = kernel
+                .iter()
+                .enumerate()
+                .map(|(i, k)| k * horizontal[(y + i) * out_width + x])
+                .sum();
+
+            out.push(sum);
+        }
+    }
+
+    out
+}
+
+/// Returns the mean SSIM and the mean contrast-structure term
+fn ssim(reference: &Plane, distorted: &Plane, peak: f64) -> Option<(f64, f64)> {
+    let (width, height) = (reference.width, reference.height);
+
+    if width < SSIM_WINDOW || height < SSIM_WINDOW {
+        return None;
+    }
+
+    let a = &reference.data;
+    let b = &distorted.data;
+
+    let c1 = (0.01 * peak).powi(2);
+    let c2 = (0.03 * peak).powi(2);
+
+    let mu_a = ssim_filter(width, height, |i| a[i]);
+    let mu_b = ssim_filter(width, height, |i| b[i]);
+    let aa = ssim_filter(width, height, |i| a[i] * a[i]);
+    let bb = ssim_filter(width, height, |i| b[i] * b[i]);
+    let ab = ssim_filter(width, height, |i| a[i] * b[i]);
+
+    let mut ssim_sum = 0.0;
+    let mut cs_sum = 0.0;
+
+    for i in 0..mu_a.len() {
+        let var_a = aa[i] - mu_a[i] * mu_a[i];
+        let var_b = bb[i] - mu_b[i] * mu_b[i];
+        let covariance = ab[i] - mu_a[i] * mu_b[i];
+
+        let l = (2.0 * mu_a[i] * mu_b[i] + c1) / (mu_a[i] * mu_a[i] + mu_b[i] * mu_b[i] + c1);
+        let cs = (2.0 * covariance + c2) / (var_a + var_b + c2);
+
+        ssim_sum += l * cs;
+        cs_sum += cs;
+    }
+
+    let n = mu_a.len() as f64;
+
+    Some((ssim_sum / n, cs_sum / n))
+}
+
+/// Weights of the scales as proposed by Wang et al.
+const MS_SSIM_WEIGHTS: [f64; 5] = [0.0448, 0.2856, 0.3001, 0.2363, 0.1333];
+
+fn ms_ssim(reference: &Plane, distorted: &Plane, peak: f64) -> Option<f64> {
+    let mut reference = reference.clone();
+    let mut distorted = distorted.clone();
+
+    let mut result = 1.0;
+
+    for (scale, weight) in MS_SSIM_WEIGHTS.into_iter().enumerate() {
+        let (ssim, cs) = ssim(&reference, &distorted, peak)?;
+
+        if scale == MS_SSIM_WEIGHTS.len() - 1 {
+            result *= ssim.max(0.0).powf(weight);
+        } else {
+            result *= cs.max(0.0).powf(weight);
+
+            reference = reference.downsample(2, 2);
+            distorted = distorted.downsample(2, 2);
+        }
+    }
+
+    Some(result)
+}
+
+fn to_xyz(color: &ColorInfo, px: &[f32; 4]) -> [f64; 3] {
+    let transfer = color.transfer();
+    let [r, g, b] = [px[0], px[1], px[2]].map(|c| transfer.scaled_to_linear(c));
+
+    // Safety: f32 is not a SIMD type
+    let xyz = unsafe { rgb_to_xyz(color.primaries().rgb_to_xyz_mat(), r, g, b) };
+
+    xyz.map(f64::from)
+}
+
+fn white_point(primaries: ColorPrimaries) -> [f64; 3] {
+    let m = primaries.rgb_to_xyz_mat();
+
+    [0, 1, 2].map(|i| f64::from(m[0][i] + m[1][i] + m[2][i]))
+}
+
+fn to_lab(xyz: [f64; 3], white: [f64; 3]) -> [f64; 3] {
+    let delta: f64 = 6.0 / 29.0;
+
+    let f = |t: f64| {
+        if t > delta.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * delta * delta) + 4.0 / 29.0
+        }
+    };
+
+    let [fx, fy, fz] = [0, 1, 2].map(|i| f(xyz[i] / white[i]));
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIEDE2000 color difference of two CIELAB colors
+fn ciede2000(lab1: [f64; 3], lab2: [f64; 3]) -> f64 {
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+
+    let pow25_7 = 25.0f64.powi(7);
+
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt());
+
+    let a1 = (1.0 + g) * a1;
+    let a2 = (1.0 + g) * a2;
+
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h.to_radians() / 2.0).sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let cos = |degrees: f64| degrees.to_radians().cos();
+
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt();
+
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+/// Convert XYZ of a PQ or HLG image to ITP (Rec. ITU-R BT.2124)
+fn to_itp(color: &ColorInfo, xyz: [f64; 3]) -> [f64; 3] {
+    // Display light in cd/m²
+    let xyz = match color.transfer() {
+        ColorTransfer::BT2100HLG => {
+            // HLG OOTF for a 1000 cd/m² display, applied to the scene luminance
+            let scene_luminance = xyz[1].max(0.0);
+            let gain = 1000.0 * scene_luminance.powf(1.2 - 1.0);
+
+            xyz.map(|c| c * gain)
+        }
+        _ => xyz,
+    };
+
+    let [x, y, z] = xyz.map(|c| c as f32);
+
+    // Safety: f32 is not a SIMD type
+    let [r, g, b] =
+        unsafe { xyz_to_rgb(ColorPrimaries::BT2020.xyz_to_rgb_mat(), x, y, z) }.map(f64::from);
+
+    let l = (1688.0 * r + 2146.0 * g + 262.0 * b) / 4096.0;
+    let m = (683.0 * r + 2951.0 * g + 462.0 * b) / 4096.0;
+    let s = (99.0 * r + 309.0 * g + 3688.0 * b) / 4096.0;
+
+    let [l, m, s] =
+        [l, m, s].map(|c| f64::from(ColorTransfer::BT2100PQ.linear_to_scaled(c as f32)));
+
+    let i = 0.5 * l + 0.5 * m;
+    let ct = (6610.0 * l - 13613.0 * m + 7003.0 * s) / 4096.0;
+    let cp = (17933.0 * l - 17390.0 * m - 543.0 * s) / 4096.0;
+
+    [i, 0.5 * ct, cp]
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Plane, ciede2000, ms_ssim, psnr, ssim};
+
+    #[test]
+    fn ciede2000_reference_values() {
+        // Test data from Sharma, Wu & Dalal, "The CIEDE2000 Color-Difference Formula"
+        let pairs = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+            ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            (
+                [22.7233, 20.0904, -46.6940],
+                [23.0331, 14.9730, -42.5619],
+                2.0373,
+            ),
+        ];
+
+        for (lab1, lab2, expected) in pairs {
+            let delta_e = ciede2000(lab1, lab2);
+
+            assert!((delta_e - expected).abs() < 1e-4, "{delta_e} != {expected}");
+        }
+    }
+
+    #[test]
+    fn identical_and_distorted_planes() {
+        let size = 192;
+
+        let reference = Plane {
+            width: size,
+            height: size,
+            data: (0..size * size)
+                .map(|i| ((i % size) * 255 / size) as f64)
+                .collect(),
+        };
+
+        assert_eq!(psnr(&reference, &reference, 255.0), f64::INFINITY);
+        assert!((ssim(&reference, &reference, 255.0).unwrap().0 - 1.0).abs() < 1e-9);
+        assert!((ms_ssim(&reference, &reference, 255.0).unwrap() - 1.0).abs() < 1e-9);
+
+        let distorted = Plane {
+            data: reference
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, v)| v + if i % 2 == 0 { 2.0 } else { -2.0 })
+                .collect(),
+            ..reference.clone()
+        };
+
+        // MSE of 4
+        let psnr = psnr(&reference, &distorted, 255.0);
+        assert!((psnr - 10.0 * (255.0f64 * 255.0 / 4.0).log10()).abs() < 1e-9);
+
+        let ssim = ssim(&reference, &distorted, 255.0).unwrap().0;
+        let ms_ssim = ms_ssim(&reference, &distorted, 255.0).unwrap();
+        assert!(ssim < 1.0 && ssim > 0.5, "{ssim}");
+        assert!(ms_ssim < 1.0 && ms_ssim > ssim, "{ms_ssim}");
+    }
+}
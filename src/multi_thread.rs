@@ -3,25 +3,34 @@ use std::mem::take;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    AnySlice, ConvertError, Image, ImageMut, ImageRef, StrictApi, convert, plane_decs::PlaneDesc,
-    verify_input_windows,
+    AnySlice, ConvertError, ConvertOptions, Image, ImageMut, ImageRef, StrictApi, convert_rows,
+    convert_with_options, plane_decs::PlaneDesc, verify_input_windows,
 };
 
-/// Parallelizes [`convert`] using as many threads as there are CPU cores.
+/// Parallelizes [`convert`](crate::convert) using as many threads as there are CPU cores.
 pub fn convert_multi_thread(
     src: &dyn ImageRef,
     dst: &mut dyn ImageMut,
+) -> Result<(), ConvertError> {
+    convert_multi_thread_with_options(src, dst, &ConvertOptions::default())
+}
+
+/// Parallelizes [`convert_with_options`] using as many threads as there are CPU cores.
+pub fn convert_multi_thread_with_options(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    options: &ConvertOptions,
 ) -> Result<(), ConvertError> {
     verify_input_windows(src, dst)?;
 
-    if src.format() == dst.format() && src.color() == dst.color() {
+    if src.format() == dst.format() && src.color() == dst.color() && !options.modifies_colors() {
         return crate::copy(src, dst);
     }
 
     let threads = num_cpus::get();
 
     if threads == 1 {
-        return convert(src, dst);
+        return convert_with_options(src, dst, options);
     }
 
     let width = src.width();
@@ -46,8 +55,21 @@ pub fn convert_multi_thread(
         threads,
     );
 
-    src_planes.into_par_iter().zip(dst_planes).try_for_each(
-        |((height, src_planes), (_, dst_planes))| {
+    // Row offset of every slice inside the image
+    let y_offsets: Vec<usize> = src_planes
+        .iter()
+        .scan(0, |y, (height, _)| {
+            let y_offset = *y;
+            *y += height;
+            Some(y_offset)
+        })
+        .collect();
+
+    src_planes
+        .into_par_iter()
+        .zip(dst_planes)
+        .zip(y_offsets)
+        .try_for_each(|(((height, src_planes), (_, dst_planes)), y_offset)| {
             let src_strides = src_planes.iter().map(|p| p.1).collect();
             let src_planes = src_planes.into_iter().map(|p| p.0).collect();
 
@@ -74,9 +96,8 @@ pub fn convert_multi_thread(
             )
             .unwrap();
 
-            convert(&src, &mut dst)
-        },
-    )
+            convert_rows(&src, &mut dst, options, y_offset)
+        })
 }
 
 pub(crate) fn split_planes<S: AnySlice>(
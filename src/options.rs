@@ -0,0 +1,121 @@
+use crate::{Lut3D, LutInterpolation};
+use std::sync::Arc;
+
+/// Options to control the behavior of [`convert_with_options`](crate::convert_with_options)
+///
+/// Construct it using [`Default`] and override the fields of interest, e.g.
+///
+/// ```
+/// # use ezk_image::ConvertOptions;
+/// let options = ConvertOptions {
+///     preserve_out_of_range: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConvertOptions {
+    /// Keep values outside the nominal range (e.g. superwhites, sub-blacks or xvYCC extended gamut colors) when
+    /// converting between transfer functions or primaries
+    ///
+    /// Negative values are passed through the transfer functions mirrored around zero, as specified by
+    /// IEC 61966-2-4 (xvYCC). The values are then only clipped when written to a format that cannot represent them,
+    /// e.g. limited range formats keep their footroom and headroom.
+    ///
+    /// By default, values outside of the nominal range are not guaranteed to survive a conversion.
+    ///
+    /// There are no floating point pixel formats, so only limited range YUV and limited range RGB
+    /// (see [`RgbColorInfo::full_range`](crate::RgbColorInfo::full_range)) destinations can hold these values. Use
+    /// [`convert_color`](crate::convert_color) to get them as unclamped floating point values instead.
+    pub preserve_out_of_range: bool,
+
+    /// Composite transparent pixels onto a background when converting a format with alpha to a format without it
+    ///
+    /// Without a background the alpha channel is simply dropped, exposing whatever color transparent pixels happen
+    /// to have.
+    pub alpha_background: Option<AlphaBackground>,
+
+    /// Blend with the [`alpha_background`](Self::alpha_background) in linear light instead of on the encoded
+    /// values of the source image
+    pub composite_in_linear_light: bool,
+
+    /// Adjust brightness, contrast, gamma, saturation, hue and white balance of the image
+    ///
+    /// Applied to the RGB values after they've been converted to the transfer function and primaries of the
+    /// destination image, before the [`lut`](Self::lut).
+    pub color_adjustments: Option<ColorAdjustments>,
+
+    /// 3D LUT applied to the RGB values after they've been converted to the transfer function and primaries of the
+    /// destination image, right before they are written
+    pub lut: Option<Arc<Lut3D>>,
+
+    /// Interpolation used when applying the [`lut`](Self::lut)
+    pub lut_interpolation: LutInterpolation,
+
+    /// Apply the [`lut`](Self::lut) to linear light values instead of values encoded with the destination's
+    /// transfer function
+    pub lut_in_linear_light: bool,
+}
+
+impl ConvertOptions {
+    /// Returns if any stage modifying the RGB values is enabled, which must be applied even when no conversion is
+    /// required
+    pub(crate) fn modifies_colors(&self) -> bool {
+        self.color_adjustments.is_some() || self.lut.is_some()
+    }
+}
+
+/// Background used to composite transparent pixels onto, see [`ConvertOptions::alpha_background`]
+///
+/// Colors are normalized RGB values (`0.0..=1.0`) using the transfer function and primaries of the source image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaBackground {
+    /// Solid color
+    Color([f32; 3]),
+
+    /// Checkerboard pattern alternating between two colors, starting with the first color in the top left corner
+    Checkerboard {
+        /// Width and height of a single square in pixels, a size of 0 is treated as 1
+        size: usize,
+        colors: [[f32; 3]; 2],
+    },
+}
+
+/// Color corrections, see [`ConvertOptions::color_adjustments`]
+///
+/// All adjustments operate on normalized RGB values (`0.0..=1.0`) encoded with the destination's transfer function.
+/// The [`Default`] value leaves the image unchanged.
+///
+/// They are applied in the order white balance, saturation & hue, contrast, brightness and gamma.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorAdjustments {
+    /// Offset added to all components, `0.0` is neutral
+    pub brightness: f32,
+
+    /// Factor to scale all components around mid-gray (`0.5`) with, `1.0` is neutral
+    pub contrast: f32,
+
+    /// Gamma correction `v^(1/gamma)`, values above `1.0` brighten the mid-tones, `1.0` is neutral
+    pub gamma: f32,
+
+    /// Factor to scale the chroma with, `0.0` results in a grayscale image, `1.0` is neutral
+    pub saturation: f32,
+
+    /// Rotation of the hue in degrees, `0.0` is neutral
+    pub hue: f32,
+
+    /// Gains for the red, green and blue components, `[1.0, 1.0, 1.0]` is neutral
+    pub white_balance: [f32; 3],
+}
+
+impl Default for ColorAdjustments {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            saturation: 1.0,
+            hue: 0.0,
+            white_balance: [1.0; 3],
+        }
+    }
+}
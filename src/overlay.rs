@@ -0,0 +1,235 @@
+use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::plane_decs::{Op, plane_layouts};
+use crate::vector::Vector;
+use crate::{
+    BoundsCheckError, ConvertError, ConvertOptions, Image, ImageMut, ImageRef, ImageRefExt,
+    PixelFormat, TransferAndPrimariesConvert, Window, need_transfer_and_primaries_convert,
+    read_any_to_rgba, rgba_to_any,
+};
+use std::ops::Range;
+
+/// Errors that may occur when calling [`overlay`]
+#[derive(Debug, thiserror::Error)]
+pub enum OverlayError {
+    #[error("overlay pixel format {0:?} has no alpha channel")]
+    MissingAlpha(PixelFormat),
+
+    #[error(transparent)]
+    BoundsCheck(#[from] BoundsCheckError),
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+}
+
+/// Alpha-blend the `overlay` onto `dst` in place, with the top left corner of the overlay placed at (`x`, `y`).
+///
+/// The overlay must be in a pixel format with alpha (e.g. [`PixelFormat::RGBA`]) and may have any size. Parts of
+/// the overlay outside of `dst` are ignored.
+///
+/// Only the region of `dst` covered by the overlay is converted, extended to the next even coordinates so that
+/// subsampled chroma samples at the overlay's edges are computed from both the blended and uncovered pixels. Only
+/// samples belonging to a pixel covered by a non-transparent overlay pixel are written, all other samples of `dst`
+/// keep their original values.
+/// Blending is done on the encoded values in the color of `dst`, the overlay is converted to the transfer and
+/// primaries of `dst` beforehand.
+pub fn overlay(
+    overlay: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    x: usize,
+    y: usize,
+) -> Result<(), OverlayError> {
+    let overlay_format = overlay.format();
+
+    if !overlay_format.has_alpha() {
+        return Err(OverlayError::MissingAlpha(overlay_format));
+    }
+
+    overlay.bounds_check()?;
+    dst.bounds_check()?;
+
+    // Odd rows or columns at the end of dst cannot be converted
+    let x_end = x.saturating_add(overlay.width()).min(dst.width() & !1);
+    let y_end = y.saturating_add(overlay.height()).min(dst.height() & !1);
+
+    if x >= x_end || y >= y_end {
+        return Ok(());
+    }
+
+    let window_x = x & !1;
+    let window_y = y & !1;
+
+    let window = Window {
+        x: window_x,
+        y: window_y,
+        width: x_end.next_multiple_of(2) - window_x,
+        height: y_end.next_multiple_of(2) - window_y,
+    };
+
+    // Copy the visible part of the overlay into a transparent image covering the whole window
+    let mut overlay_window =
+        Image::blank(overlay_format, window.width, window.height, overlay.color());
+
+    // Pixels of the window the overlay contributes to, only samples belonging to them are written back into dst
+    let mut covered = vec![false; window.width * window.height];
+
+    {
+        let (src_plane, src_stride) = overlay.planes().next().expect("overlay has one plane");
+        let (window_plane, window_stride) = overlay_window
+            .planes_mut()
+            .next()
+            .expect("overlay has one plane");
+
+        let desc = &overlay_format.plane_desc()[0];
+        let bytes_per_pixel = desc.packed_stride(1);
+        let row_len = (x_end - x) * bytes_per_pixel;
+
+        for row in 0..y_end - y {
+            let src_offset = row * src_stride;
+            let window_offset =
+                (row + y - window_y) * window_stride + (x - window_x) * bytes_per_pixel;
+
+            window_plane[window_offset..][..row_len]
+                .copy_from_slice(&src_plane[src_offset..][..row_len]);
+        }
+
+        let alpha_offset =
+            overlay_format.alpha_index().expect("overlay has alpha") * desc.bytes_per_primitive;
+
+        for (row, covered) in covered.chunks_exact_mut(window.width).enumerate() {
+            let window_row = &window_plane[row * window_stride..];
+
+            for (column, covered) in covered.iter_mut().enumerate() {
+                let alpha = &window_row[column * bytes_per_pixel + alpha_offset..]
+                    [..desc.bytes_per_primitive];
+
+                *covered = alpha.iter().any(|&byte| byte != 0);
+            }
+        }
+    }
+
+    let dst_format = dst.format();
+    let dst_color = dst.color();
+
+    let mut overlay_reader = read_any_to_rgba(&overlay_window)?;
+
+    if need_transfer_and_primaries_convert(&overlay.color(), &dst_color) {
+        overlay_reader = Box::new(TransferAndPrimariesConvert::new(
+            &overlay.color(),
+            &dst_color,
+            &ConvertOptions::default(),
+            overlay_reader,
+        ));
+    }
+
+    // Convert the window into a separate image and copy the result back, instead of cropping dst, so that windows
+    // touching the end of a plane pass the bounds checks
+    let mut blended = Image::blank(dst_format, window.width, window.height, dst_color);
+
+    let reader = Blend {
+        background: read_any_to_rgba(&*dst)?,
+        background_x: window.x,
+        background_y: window.y,
+        overlay: overlay_reader,
+    };
+
+    rgba_to_any(&mut blended, reader)?;
+
+    let is_covered = |mut rows: Range<usize>, columns: Range<usize>| {
+        rows.any(|row| {
+            covered[row * window.width..][columns.clone()]
+                .iter()
+                .any(|&covered| covered)
+        })
+    };
+
+    for (((plane, stride), (blended_plane, blended_stride)), (desc, layout)) in
+        dst.planes_mut().zip(blended.planes()).zip(
+            dst_format
+                .plane_desc()
+                .iter()
+                .zip(plane_layouts(dst_format)),
+        )
+    {
+        let rows_per_sample = match desc.height_op {
+            Op::Div(n) => n,
+            _ => 1,
+        };
+
+        let group_len = layout.primitives.len();
+        let first_group = window.x / layout.width;
+        let first_row = desc.height_op.op(window.y);
+
+        for row in 0..desc.height_op.op(window.height) {
+            let pixel_rows = row * rows_per_sample..(row + 1) * rows_per_sample;
+
+            let plane_row = &mut plane[(first_row + row) * stride..];
+            let blended_row = &blended_plane[row * blended_stride..];
+
+            for group in 0..window.width / layout.width {
+                let group_x = group * layout.width;
+
+                for (i, &(_, start, end)) in layout.primitives.iter().enumerate() {
+                    // Keep the original sample if none of its pixels are covered by the overlay
+                    if !is_covered(pixel_rows.clone(), group_x + start..group_x + end) {
+                        continue;
+                    }
+
+                    let dst_index =
+                        ((first_group + group) * group_len + i) * desc.bytes_per_primitive;
+                    let blended_index = (group * group_len + i) * desc.bytes_per_primitive;
+
+                    plane_row[dst_index..][..desc.bytes_per_primitive]
+                        .copy_from_slice(&blended_row[blended_index..][..desc.bytes_per_primitive]);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Composites `overlay` over `background` using the porter-duff over operator
+struct Blend<B, O> {
+    background: B,
+
+    /// Offset of the overlay inside the background
+    background_x: usize,
+    background_y: usize,
+
+    overlay: O,
+}
+
+impl<B, O> Blend<B, O> {
+    #[inline(always)]
+    unsafe fn blend<V: Vector>(background: RgbaPixel<V>, overlay: RgbaPixel<V>) -> RgbaPixel<V> {
+        let background = background.premultiply();
+        let overlay = overlay.premultiply();
+
+        let transparency = V::splat(1.0).vsub(overlay.a);
+
+        RgbaPixel {
+            r: background.r.vmul(transparency).vadd(overlay.r),
+            g: background.g.vmul(transparency).vadd(overlay.g),
+            b: background.b.vmul(transparency).vadd(overlay.b),
+            a: background.a.vmul(transparency).vadd(overlay.a),
+        }
+        .unpremultiply()
+    }
+}
+
+impl<B: RgbaSrc, O: RgbaSrc> RgbaSrc for Blend<B, O> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let background = self
+            .background
+            .read::<V>(self.background_x + x, self.background_y + y);
+        let overlay = self.overlay.read::<V>(x, y);
+
+        RgbaBlock {
+            px00: Self::blend(background.px00, overlay.px00),
+            px01: Self::blend(background.px01, overlay.px01),
+            px10: Self::blend(background.px10, overlay.px10),
+            px11: Self::blend(background.px11, overlay.px11),
+        }
+    }
+}
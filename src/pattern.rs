@@ -0,0 +1,236 @@
+use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::vector::Vector;
+use crate::{ColorTransfer, ConvertError, ImageMut, rgba_to_any};
+use std::f32::consts::PI;
+
+/// Test patterns which can be written into any image using [`fill_test_pattern`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// SMPTE RP 219 HD color bars: 75% bars, 100% cyan/blue/yellow/red side patches, a luma ramp and a PLUGE
+    SmpteBars,
+    /// EBU 100/0/75/0 color bars
+    EbuBars,
+    /// Horizontal gray ramp from black on the left to white on the right
+    Ramp,
+    /// Circular zone plate, the frequency increases from the center until it reaches the Nyquist frequency at the
+    /// left and right (or top and bottom) edges
+    ZonePlate,
+    /// ITU-R BT.814 style picture line-up signal on a black background: -2%, +2% and +4% bars next to a white patch
+    Pluge,
+    /// ITU-R BT.2111 style HDR color bars, using the reference levels of the image's transfer function (PQ or HLG)
+    ///
+    /// The layout follows SMPTE RP 219, with 75% bars at HDR reference white (58% for PQ, 75% for HLG).
+    Bt2111,
+}
+
+/// Fill `dst` with a test pattern
+///
+/// Patterns are defined as non-linear R'G'B' signal levels and written as is, in the transfer function and primaries
+/// of `dst`. Levels below black (e.g. the -2% PLUGE bar) are only preserved by limited range formats.
+pub fn fill_test_pattern(dst: &mut dyn ImageMut, pattern: TestPattern) -> Result<(), ConvertError> {
+    if dst.width() % 2 == 1 || dst.height() % 2 == 1 {
+        return Err(ConvertError::OddImageDimensions);
+    }
+
+    let src = PatternSrc {
+        pattern,
+        width: dst.width() as f32,
+        height: dst.height() as f32,
+        levels: Levels::new(pattern, dst.color().transfer()),
+    };
+
+    rgba_to_any(dst, src)
+}
+
+/// Signal levels of color bars
+#[derive(Clone, Copy)]
+struct Levels {
+    /// Level of the reduced amplitude bars
+    bars: f32,
+    /// Level of the full amplitude bars
+    full: f32,
+    /// Gray of the side patches
+    gray: f32,
+}
+
+impl Levels {
+    fn new(pattern: TestPattern, transfer: ColorTransfer) -> Self {
+        match (pattern, transfer) {
+            (TestPattern::Bt2111, ColorTransfer::BT2100PQ) => Self {
+                bars: 0.58,
+                full: 0.75,
+                gray: 0.4,
+            },
+            _ => Self {
+                bars: 0.75,
+                full: 1.0,
+                gray: 0.4,
+            },
+        }
+    }
+}
+
+struct PatternSrc {
+    pattern: TestPattern,
+    width: f32,
+    height: f32,
+    levels: Levels,
+}
+
+/// Order of the bars from left to right as RGB multipliers
+const BARS: [[f32; 3]; 7] = [
+    [1.0, 1.0, 1.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 1.0],
+    [0.0, 1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0],
+];
+
+impl PatternSrc {
+    fn pixel(&self, x: usize, y: usize) -> [f32; 3] {
+        // Normalized coordinates of the pixel's center
+        let u = (x as f32 + 0.5) / self.width;
+        let v = (y as f32 + 0.5) / self.height;
+
+        match self.pattern {
+            TestPattern::SmpteBars | TestPattern::Bt2111 => self.smpte_bars(u, v),
+            TestPattern::EbuBars => {
+                let bar = ((u * 8.0) as usize).min(7);
+
+                match bar {
+                    0 => [1.0; 3],
+                    7 => [0.0; 3],
+                    _ => BARS[bar].map(|c| c * 0.75),
+                }
+            }
+            TestPattern::Ramp => [x as f32 / (self.width - 1.0).max(1.0); 3],
+            TestPattern::ZonePlate => {
+                let dx = x as f32 + 0.5 - self.width / 2.0;
+                let dy = y as f32 + 0.5 - self.height / 2.0;
+
+                // The phase's derivative (the frequency) reaches PI (Nyquist) at the edge of the longer side
+                let radius = self.width.max(self.height) / 2.0;
+                let phase = PI * (dx * dx + dy * dy) / (2.0 * radius);
+
+                [0.5 + 0.5 * phase.cos(); 3]
+            }
+            TestPattern::Pluge => {
+                let in_band =
+                    |start: f32| (start..start + 0.1).contains(&u) && (0.2..0.8).contains(&v);
+
+                let level = if in_band(0.2) {
+                    -0.02
+                } else if in_band(0.35) {
+                    0.02
+                } else if in_band(0.5) {
+                    0.04
+                } else if (0.7..0.8).contains(&u) && (0.4..0.6).contains(&v) {
+                    1.0
+                } else {
+                    0.0
+                };
+
+                [level; 3]
+            }
+        }
+    }
+
+    fn smpte_bars(&self, u: f32, v: f32) -> [f32; 3] {
+        let Levels { bars, full, gray } = self.levels;
+
+        // Side patches are 1/8 of the width, the 7 bars share the rest
+        let side = 1.0 / 8.0;
+        let bar_width = (1.0 - 2.0 * side) / 7.0;
+
+        let left = u < side;
+        let right = u >= 1.0 - side;
+
+        // Position in units of bars from the left edge of the first bar
+        let c = (u - side) / bar_width;
+
+        if v < 7.0 / 12.0 {
+            if left || right {
+                [gray; 3]
+            } else {
+                BARS[(c as usize).min(6)].map(|c| c * bars)
+            }
+        } else if v < 8.0 / 12.0 {
+            if left {
+                [0.0, full, full]
+            } else if right {
+                [0.0, 0.0, full]
+            } else {
+                [bars; 3]
+            }
+        } else if v < 9.0 / 12.0 {
+            if left {
+                [full, full, 0.0]
+            } else if right {
+                [full, 0.0, 0.0]
+            } else {
+                [(c / 7.0).clamp(0.0, 1.0) * full; 3]
+            }
+        } else {
+            let level = if left || right {
+                0.15 * full
+            } else if c < 1.5 {
+                0.0
+            } else if c < 3.5 {
+                full
+            } else if c < 3.5 + 5.0 / 6.0 {
+                0.0
+            } else {
+                // PLUGE, 5 steps each a third of a bar wide, followed by a bar of black
+                let step = ((c - (3.5 + 5.0 / 6.0)) * 3.0) as usize;
+
+                [-0.02, 0.0, 0.02, 0.0, 0.04]
+                    .get(step)
+                    .copied()
+                    .unwrap_or(0.0)
+            };
+
+            [level; 3]
+        }
+    }
+
+    /// Evaluate the pattern for `V::LEN` pixels starting at (x, y)
+    #[inline(always)]
+    unsafe fn read_pixels<V: Vector>(&self, x: usize, y: usize) -> RgbaPixel<V> {
+        let mut r = [0.0f32; 16];
+        let mut g = [0.0f32; 16];
+        let mut b = [0.0f32; 16];
+
+        for (i, ((r, g), b)) in r
+            .iter_mut()
+            .zip(&mut g)
+            .zip(&mut b)
+            .take(V::LEN)
+            .enumerate()
+        {
+            [*r, *g, *b] = self.pixel(x + i, y);
+        }
+
+        let lanes = V::lane_indices();
+
+        RgbaPixel {
+            r: V::gather(r.as_ptr(), lanes),
+            g: V::gather(g.as_ptr(), lanes),
+            b: V::gather(b.as_ptr(), lanes),
+            a: V::splat(1.0),
+        }
+    }
+}
+
+impl RgbaSrc for PatternSrc {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        RgbaBlock {
+            px00: self.read_pixels(x, y),
+            px01: self.read_pixels(x + V::LEN, y),
+            px10: self.read_pixels(x, y + 1),
+            px11: self.read_pixels(x + V::LEN, y + 1),
+        }
+    }
+}
@@ -201,6 +201,11 @@ impl PixelFormat {
         }
     }
 
+    /// Number of bits used by each sample of the format
+    ///
+    /// [`ColorSpace::YCoCgR`](crate::ColorSpace::YCoCgR) is the exception: its Cg and Co samples use one bit more
+    /// than this and range from `0` to `2^(N+1) - 1`. It is therefore only accepted for formats with 16 bit
+    /// primitives, which have room for the extra bit.
     pub fn bits_per_component(&self) -> usize {
         match self {
             #[cfg(feature = "I420")]
@@ -244,6 +249,26 @@ impl PixelFormat {
         }
     }
 
+    /// Returns if the format has an alpha channel
+    pub fn has_alpha(&self) -> bool {
+        self.alpha_index().is_some()
+    }
+
+    /// Index of the alpha component inside an interleaved pixel
+    pub(crate) fn alpha_index(&self) -> Option<usize> {
+        match self {
+            #[cfg(feature = "RGBA")]
+            PixelFormat::RGBA => Some(3),
+            #[cfg(feature = "BGRA")]
+            PixelFormat::BGRA => Some(3),
+            #[cfg(feature = "ARGB")]
+            PixelFormat::ARGB => Some(0),
+            #[cfg(feature = "ABGR")]
+            PixelFormat::ABGR => Some(0),
+            _ => None,
+        }
+    }
+
     pub(crate) fn plane_desc(&self) -> &'static [PlaneDesc] {
         use PixelFormat::*;
 
@@ -289,6 +314,35 @@ impl PixelFormat {
         }
     }
 
+    /// Chroma subsampling of the format, horizontally and vertically
+    pub(crate) fn subsampling(&self) -> (usize, usize) {
+        use PixelFormat::*;
+
+        match self {
+            #[cfg(feature = "I420")]
+            I420 => (2, 2),
+            #[cfg(feature = "I422")]
+            I422 => (2, 1),
+            #[cfg(feature = "I010")]
+            I010 => (2, 2),
+            #[cfg(feature = "I012")]
+            I012 => (2, 2),
+            #[cfg(feature = "I210")]
+            I210 => (2, 1),
+            #[cfg(feature = "I212")]
+            I212 => (2, 1),
+            #[cfg(feature = "NV12")]
+            NV12 => (2, 2),
+            #[cfg(feature = "P010")]
+            P010 => (2, 2),
+            #[cfg(feature = "P012")]
+            P012 => (2, 2),
+            #[cfg(feature = "YUYV")]
+            YUYV => (2, 1),
+            _ => (1, 1),
+        }
+    }
+
     pub fn variants() -> impl IntoIterator<Item = Self> {
         use PixelFormat::*;
 
@@ -335,7 +389,7 @@ impl PixelFormat {
     }
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum BoundsCheckError {
     #[error(transparent)]
     InvalidNumberOfPlanes(#[from] InvalidNumberOfPlanesError),
@@ -1,4 +1,5 @@
-use crate::StrictApi as _;
+use crate::statistics::Channel;
+use crate::{PixelFormat, StrictApi as _};
 #[cfg(feature = "resize")]
 use fir::PixelType::*;
 
@@ -244,3 +245,76 @@ pub(crate) const RGB_PLANES: [PlaneDesc; 1] = [PlaneDesc {
     #[cfg(feature = "resize")]
     pixel_type: U8x3,
 }];
+
+/// Layout of a plane as repeating groups of primitives
+pub(crate) struct PlaneLayout {
+    /// Number of pixels covered by a group
+    pub(crate) width: usize,
+    /// Channel of every primitive of a group and the range of pixels inside the group it belongs to
+    pub(crate) primitives: &'static [(Channel, usize, usize)],
+}
+
+pub(crate) fn plane_layouts(format: PixelFormat) -> &'static [PlaneLayout] {
+    use Channel::*;
+
+    const fn layout(width: usize, primitives: &'static [(Channel, usize, usize)]) -> PlaneLayout {
+        PlaneLayout { width, primitives }
+    }
+
+    // Vertical subsampling is taken from the plane descriptions
+    const Y_PLANE: PlaneLayout = layout(1, &[(Y, 0, 1)]);
+
+    const YUV_SUBSAMPLED: &[PlaneLayout] =
+        &[Y_PLANE, layout(2, &[(U, 0, 2)]), layout(2, &[(V, 0, 2)])];
+    const YUV444: &[PlaneLayout] = &[Y_PLANE, layout(1, &[(U, 0, 1)]), layout(1, &[(V, 0, 1)])];
+    const YUV_2PLANE: &[PlaneLayout] = &[Y_PLANE, layout(2, &[(U, 0, 2), (V, 0, 2)])];
+    const YUYV: &[PlaneLayout] = &[layout(2, &[(Y, 0, 1), (U, 0, 2), (Y, 1, 2), (V, 0, 2)])];
+
+    const RGBA: &[PlaneLayout] = &[layout(1, &[(R, 0, 1), (G, 0, 1), (B, 0, 1), (A, 0, 1)])];
+    const BGRA: &[PlaneLayout] = &[layout(1, &[(B, 0, 1), (G, 0, 1), (R, 0, 1), (A, 0, 1)])];
+    const ARGB: &[PlaneLayout] = &[layout(1, &[(A, 0, 1), (R, 0, 1), (G, 0, 1), (B, 0, 1)])];
+    const ABGR: &[PlaneLayout] = &[layout(1, &[(A, 0, 1), (B, 0, 1), (G, 0, 1), (R, 0, 1)])];
+    const RGB: &[PlaneLayout] = &[layout(1, &[(R, 0, 1), (G, 0, 1), (B, 0, 1)])];
+    const BGR: &[PlaneLayout] = &[layout(1, &[(B, 0, 1), (G, 0, 1), (R, 0, 1)])];
+
+    match format {
+        #[cfg(feature = "I420")]
+        PixelFormat::I420 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I422")]
+        PixelFormat::I422 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I444")]
+        PixelFormat::I444 => YUV444,
+        #[cfg(feature = "I010")]
+        PixelFormat::I010 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I012")]
+        PixelFormat::I012 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I210")]
+        PixelFormat::I210 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I212")]
+        PixelFormat::I212 => YUV_SUBSAMPLED,
+        #[cfg(feature = "I410")]
+        PixelFormat::I410 => YUV444,
+        #[cfg(feature = "I412")]
+        PixelFormat::I412 => YUV444,
+        #[cfg(feature = "NV12")]
+        PixelFormat::NV12 => YUV_2PLANE,
+        #[cfg(feature = "P010")]
+        PixelFormat::P010 => YUV_2PLANE,
+        #[cfg(feature = "P012")]
+        PixelFormat::P012 => YUV_2PLANE,
+        #[cfg(feature = "YUYV")]
+        PixelFormat::YUYV => YUYV,
+        #[cfg(feature = "RGBA")]
+        PixelFormat::RGBA => RGBA,
+        #[cfg(feature = "BGRA")]
+        PixelFormat::BGRA => BGRA,
+        #[cfg(feature = "ARGB")]
+        PixelFormat::ARGB => ARGB,
+        #[cfg(feature = "ABGR")]
+        PixelFormat::ABGR => ABGR,
+        #[cfg(feature = "RGB")]
+        PixelFormat::RGB => RGB,
+        #[cfg(feature = "BGR")]
+        PixelFormat::BGR => BGR,
+    }
+}
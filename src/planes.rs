@@ -8,7 +8,7 @@ use crate::{
 };
 use std::mem::MaybeUninit;
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 #[error("got invalid number of planes, expected {expected} but only got {got}")]
 pub struct InvalidNumberOfPlanesError {
     pub expected: usize,
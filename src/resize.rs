@@ -1,10 +1,18 @@
-use crate::{ImageMut, ImageRef, PixelFormat};
+use crate::formats::DynRgbaReader;
+use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::formats::visit_2x2::{Image2x2Visitor, visit};
+use crate::vector::Vector;
+use crate::{
+    BoundsCheckError, ColorInfo, ColorTransfer, ConvertError, ConvertOptions, FillError, Image,
+    ImageMut, ImageRef, ImageRefExt, PixelFormat, TransferAndPrimariesConvert, Window, clear, fill,
+    need_transfer_and_primaries_convert, read_any_to_rgba, rgba_to_any,
+};
 use fir::{IntoImageView, IntoImageViewMut, pixels::InnerPixel};
 #[cfg(feature = "multi-thread")]
 use rayon::scope;
 #[cfg(not(feature = "multi-thread"))]
 use rayon_stub::scope;
-use std::{cmp, error::Error, fmt, marker::PhantomData};
+use std::{borrow::Cow, cmp, error::Error, fmt, marker::PhantomData};
 
 pub use fir::{Filter, FilterType, ResizeAlg};
 
@@ -12,6 +20,17 @@ pub use fir::{Filter, FilterType, ResizeAlg};
 #[derive(Debug, PartialEq)]
 pub enum ResizeError {
     DifferentFormats(PixelFormat, PixelFormat),
+    BoundsCheck(BoundsCheckError),
+    InvalidPaddingColor,
+    InvalidSourceRect(SourceRect),
+    OddImageDimensions,
+    InvalidColorInfo,
+}
+
+impl From<BoundsCheckError> for ResizeError {
+    fn from(error: BoundsCheckError) -> Self {
+        ResizeError::BoundsCheck(error)
+    }
 }
 
 impl fmt::Display for ResizeError {
@@ -21,27 +40,133 @@ impl fmt::Display for ResizeError {
                 f,
                 "source and destination images have different pixel formats (source image is {src:?} and destination is {dst:?})"
             ),
+            ResizeError::BoundsCheck(error) => error.fmt(f),
+            ResizeError::InvalidPaddingColor => write!(
+                f,
+                "padding color cannot be converted to the color of the destination image"
+            ),
+            ResizeError::InvalidSourceRect(rect) => write!(
+                f,
+                "source rect {rect:?} is empty or goes out of the source image's bounds"
+            ),
+            ResizeError::OddImageDimensions => write!(
+                f,
+                "image dimensions must be divisible by 2 to resize in linear light"
+            ),
+            ResizeError::InvalidColorInfo => write!(f, "invalid color info for pixel format"),
         }
     }
 }
 
 impl Error for ResizeError {}
 
+/// Position of subsampled chroma samples relative to the luma samples they belong to
+///
+/// Planes are resized independently, which keeps chroma samples centered between their luma samples in place. Other
+/// sitings must be compensated, as the distance between a chroma sample and its luma samples changes with the scale.
+/// [`PixelFormat::YUYV`] stores chroma and luma in the same plane and is always resized as if centered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChromaSiting {
+    /// Chroma samples are centered between their luma samples (JPEG, MPEG-1), as written by
+    /// [`convert`](crate::convert)
+    #[default]
+    Center,
+    /// Chroma samples are co-sited with the left luma sample and vertically centered (MPEG-2, H.264 and HEVC default)
+    Left,
+    /// Chroma samples are co-sited with the top left luma sample (BT.2020 and BT.2100)
+    TopLeft,
+}
+
+impl ChromaSiting {
+    /// Horizontal and vertical offset of a chroma sample from the center between its luma samples, in chroma samples
+    fn offsets(self) -> (f64, f64) {
+        match self {
+            ChromaSiting::Center => (0.0, 0.0),
+            ChromaSiting::Left => (-0.25, 0.0),
+            ChromaSiting::TopLeft => (-0.25, -0.25),
+        }
+    }
+}
+
+/// Area of the source image to resize with [`Resizer::resize_cropped`]
+///
+/// Coordinates are in pixels of the full resolution (luma) plane and may be fractional. They are mapped to the
+/// coordinates of subsampled planes by dividing them by the subsampling factor, which keeps the edges of the area
+/// aligned in all planes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// How [`Resizer::fit`] handles images with different aspect ratios
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FitMode {
+    /// Scale the whole source image into the destination, padding the remaining area (letterbox or pillarbox)
+    Contain(Padding),
+    /// Scale the source image to cover the whole destination, cropping what doesn't fit
+    Cover,
+}
+
+/// Color of the padding added by [`FitMode::Contain`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Padding {
+    /// Black in the color of the destination image
+    Black,
+    /// Code values of a color with `bits` bits per component, see [`fill`](crate::fill)
+    Color {
+        color: ColorInfo,
+        bits: usize,
+        value: [f32; 3],
+    },
+}
+
 /// Wrapper over [`fast_image_resize`](fir)
 #[derive(Clone)]
 pub struct Resizer {
     alg: fir::ResizeAlg,
+    chroma_siting: ChromaSiting,
+    linear_light: bool,
     fir: Vec<fir::Resizer>,
 }
 
 impl Resizer {
     pub fn new(alg: fir::ResizeAlg) -> Self {
-        Self { alg, fir: vec![] }
+        Self {
+            alg,
+            chroma_siting: ChromaSiting::default(),
+            linear_light: false,
+            fir: vec![],
+        }
+    }
+
+    /// Set the [`ChromaSiting`] of images with subsampled chroma, so that their chroma stays aligned with the luma
+    /// samples when scaled
+    pub fn with_chroma_siting(mut self, chroma_siting: ChromaSiting) -> Self {
+        self.chroma_siting = chroma_siting;
+        self
+    }
+
+    /// Filter linear light instead of the values encoded with the images' transfer functions
+    ///
+    /// Avoids darkened fine detail and wrongly colored edges when downscaling high contrast content. The source is
+    /// converted to a temporary linear RGBA image with f32 components, which is resized and then converted into the
+    /// destination's color, including its transfer function and primaries. This is a lot slower and requires image
+    /// dimensions divisible by 2. [`ChromaSiting`] is ignored, as chroma is upsampled before resizing.
+    pub fn with_linear_light(mut self, linear_light: bool) -> Self {
+        self.linear_light = linear_light;
+        self
     }
 
     /// Resize an image. `src` and `dst` must have the same pixel format.
     ///
-    /// Color characteristics of the images are ignored.
+    /// Color characteristics of the images are ignored unless resizing in
+    /// [linear light](Self::with_linear_light), except for
+    /// [`premultiplied_alpha`](crate::RgbColorInfo::premultiplied_alpha). Images with straight alpha are
+    /// premultiplied before and unpremultiplied after resizing, so that transparent pixels don't bleed into their
+    /// neighbors.
     pub fn resize(
         &mut self,
         src: &dyn ImageRef,
@@ -51,15 +176,238 @@ impl Resizer {
             return Err(ResizeError::DifferentFormats(src.format(), dst.format()));
         }
 
+        let dst_window = Window {
+            x: 0,
+            y: 0,
+            width: dst.width(),
+            height: dst.height(),
+        };
+
+        self.resize_window(src, None, dst, dst_window)?;
+
+        Ok(())
+    }
+
+    /// Resize the `rect` area of `src` to `dst`, e.g. for digital zoom or pan and scan.
+    ///
+    /// `rect` may have fractional coordinates, but must not be empty or exceed the bounds of `src`. See
+    /// [`Resizer::resize`] for details.
+    pub fn resize_cropped(
+        &mut self,
+        src: &dyn ImageRef,
+        dst: &mut dyn ImageMut,
+        rect: SourceRect,
+    ) -> Result<(), ResizeError> {
+        if src.format() != dst.format() {
+            return Err(ResizeError::DifferentFormats(src.format(), dst.format()));
+        }
+
+        let SourceRect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+
+        let valid = [x, y, width, height].iter().all(|v| v.is_finite())
+            && x >= 0.0
+            && y >= 0.0
+            && width > 0.0
+            && height > 0.0
+            && x + width <= src.width() as f64
+            && y + height <= src.height() as f64;
+
+        if !valid {
+            return Err(ResizeError::InvalidSourceRect(rect));
+        }
+
+        let dst_window = Window {
+            x: 0,
+            y: 0,
+            width: dst.width(),
+            height: dst.height(),
+        };
+
+        self.resize_window(src, Some(rect), dst, dst_window)?;
+
+        Ok(())
+    }
+
+    /// Resize `src` into `dst` preserving the aspect ratio of `src`. `src` and `dst` must have the same pixel format.
+    ///
+    /// The scaled image is centered in `dst`, its position and size are aligned to the chroma subsampling of the
+    /// pixel format. With [`FitMode::Contain`] the padding is written directly into the planes of `dst` (see
+    /// [`fill`](crate::fill)). With [`FitMode::Cover`] the area of `src` with the aspect ratio of `dst` is cropped
+    /// from its center, see [`Resizer::resize_cropped`].
+    ///
+    /// See [`Resizer::resize`] for details.
+    pub fn fit(
+        &mut self,
+        src: &dyn ImageRef,
+        dst: &mut dyn ImageMut,
+        mode: FitMode,
+    ) -> Result<(), ResizeError> {
+        if src.format() != dst.format() {
+            return Err(ResizeError::DifferentFormats(src.format(), dst.format()));
+        }
+
+        src.bounds_check()?;
+        dst.bounds_check()?;
+
+        // Linear light resizing converts the scaled window as a whole image, which must have even dimensions
+        let (align_x, align_y) = if self.linear_light {
+            (2, 2)
+        } else {
+            dst.format().subsampling()
+        };
+
+        let (src_width, src_height) = (src.width(), src.height());
+        let (dst_width, dst_height) = (dst.width(), dst.height());
+
+        let scale_x = dst_width as f64 / src_width as f64;
+        let scale_y = dst_height as f64 / src_height as f64;
+
+        match mode {
+            FitMode::Contain(padding) => {
+                let scale = scale_x.min(scale_y);
+
+                let width = align(src_width as f64 * scale, align_x).min(dst_width);
+                let height = align(src_height as f64 * scale, align_y).min(dst_height);
+
+                let x = (dst_width - width) / 2 / align_x * align_x;
+                let y = (dst_height - height) / 2 / align_y * align_y;
+
+                let bars = [
+                    (0, 0, dst_width, y),
+                    (0, y + height, dst_width, dst_height - y - height),
+                    (0, y, x, height),
+                    (x + width, y, dst_width - x - width, height),
+                ];
+
+                for (x, y, width, height) in bars {
+                    if width == 0 || height == 0 {
+                        continue;
+                    }
+
+                    let window = Some(Window {
+                        x,
+                        y,
+                        width,
+                        height,
+                    });
+
+                    let result = match padding {
+                        Padding::Black => clear(dst, window),
+                        Padding::Color { color, bits, value } => {
+                            fill(dst, &color, bits, value, window)
+                        }
+                    };
+
+                    result.map_err(|e| match e {
+                        FillError::BoundsCheck(e) => ResizeError::BoundsCheck(e),
+                        FillError::Convert(_) => ResizeError::InvalidPaddingColor,
+                        FillError::WindowOutOfBounds => unreachable!("bars are inside of dst"),
+                    })?;
+                }
+
+                if width > 0 && height > 0 {
+                    let dst_window = Window {
+                        x,
+                        y,
+                        width,
+                        height,
+                    };
+
+                    self.resize_window(src, None, dst, dst_window)?;
+                }
+            }
+            FitMode::Cover => {
+                let scale = scale_x.max(scale_y);
+
+                let width = (dst_width as f64 / scale).min(src_width as f64);
+                let height = (dst_height as f64 / scale).min(src_height as f64);
+
+                let src_crop = SourceRect {
+                    x: (src_width as f64 - width) / 2.0,
+                    y: (src_height as f64 - height) / 2.0,
+                    width,
+                    height,
+                };
+
+                let dst_window = Window {
+                    x: 0,
+                    y: 0,
+                    width: dst_width,
+                    height: dst_height,
+                };
+
+                self.resize_window(src, Some(src_crop), dst, dst_window)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resize the `src_crop` area of `src` (or all of it) into the `dst_window` area of `dst`
+    ///
+    /// Both must be inside of their images, `dst_window` must be aligned to the chroma subsampling of the format.
+    fn resize_window(
+        &mut self,
+        src: &dyn ImageRef,
+        src_crop: Option<SourceRect>,
+        dst: &mut dyn ImageMut,
+        dst_window: Window,
+    ) -> Result<(), ResizeError> {
+        src.bounds_check()?;
+        dst.bounds_check()?;
+
+        if self.linear_light {
+            return self.resize_linear(src, src_crop, dst, dst_window);
+        }
+
         let alg = self.alg;
+        let chroma_siting = self.chroma_siting;
 
         let desc = src.format().plane_desc();
 
         let src_width = src.width();
-        let dst_width = dst.width();
+        let src_height = src.height();
+
+        let src_premultiplied = src.color().premultiplied_alpha();
+        let dst_premultiplied = dst.color().premultiplied_alpha();
+
+        let alpha_index = src.format().alpha_index();
+
+        // fir can only handle straight alpha in the last component, everything else is done here
+        let mul_div_alpha = alpha_index == Some(3) && !src_premultiplied && !dst_premultiplied;
+        let premultiply_src = alpha_index.is_some() && !mul_div_alpha && !src_premultiplied;
+        let unpremultiply_dst = alpha_index.is_some() && !mul_div_alpha && !dst_premultiplied;
+
+        let mut src_planes: Vec<(&[u8], usize)> = src.planes().collect();
+
+        // Start every plane of dst at the top left corner of the window
+        let mut dst_planes: Vec<(&mut [u8], usize)> = dst
+            .planes_mut()
+            .zip(desc)
+            .map(|((plane, stride), plane_desc)| {
+                let offset = plane_desc.height_op.op(dst_window.y) * stride
+                    + plane_desc.width_op.op(dst_window.x) * plane_desc.bytes_per_primitive;
+
+                (&mut plane[offset..], stride)
+            })
+            .collect();
+
+        let premultiplied_src_plane;
 
-        let src_planes: Vec<(&[u8], usize)> = src.planes().collect();
-        let dst_planes: Vec<(&mut [u8], usize)> = dst.planes_mut().collect();
+        if let (true, Some(alpha_index)) = (premultiply_src, alpha_index) {
+            let (plane, stride) = src_planes[0];
+
+            let mut plane = plane.to_vec();
+            premultiply_rgba8(&mut plane, stride, src_width, src_height, alpha_index);
+
+            premultiplied_src_plane = plane;
+            src_planes[0] = (&premultiplied_src_plane, stride);
+        }
 
         self.fir.resize_with(
             cmp::max(self.fir.len(), src_planes.len()),
@@ -67,32 +415,104 @@ impl Resizer {
         );
 
         scope(|s| {
-            for ((plane_desc, ((src_plane, src_stride), (dst_plane, dst_stride))), fir_resizer) in
-                desc.iter()
-                    .zip(src_planes.into_iter().zip(dst_planes))
-                    .zip(&mut self.fir)
+            for (
+                plane_index,
+                ((plane_desc, ((src_plane, src_stride), (dst_plane, dst_stride))), fir_resizer),
+            ) in desc
+                .iter()
+                .zip(src_planes.into_iter().zip(&mut dst_planes))
+                .zip(&mut self.fir)
+                .enumerate()
             {
+                let dst_plane = &mut **dst_plane;
+                let dst_stride = *dst_stride;
+
                 s.spawn(move |_| {
-                    let src_fir_width = plane_desc.width_op.op(src_width)
-                        / (plane_desc.pixel_type.size() / plane_desc.bytes_per_primitive);
-                    let dst_fir_width = plane_desc.width_op.op(dst_width)
-                        / (plane_desc.pixel_type.size() / plane_desc.bytes_per_primitive);
+                    // Convert a horizontal position or width in pixels to fir pixels of the plane
+                    let fir_x = |x: usize| {
+                        plane_desc.width_op.op(x)
+                            / (plane_desc.pixel_type.size() / plane_desc.bytes_per_primitive)
+                    };
+
+                    // Scale of the plane's fir pixels relative to the image's pixels
+                    let scale_x = fir_x(2) as f64 / 2.0;
+                    let scale_y = plane_desc.height_op.op(2) as f64 / 2.0;
+
+                    let crop = src_crop.unwrap_or(SourceRect {
+                        x: 0.0,
+                        y: 0.0,
+                        width: src_width as f64,
+                        height: src_height as f64,
+                    });
+
+                    // Chroma samples which aren't centered between their luma samples must be moved by a fraction of
+                    // the scaling, otherwise they'd shift relative to the luma samples
+                    let (siting_x, siting_y) = if plane_index > 0 {
+                        chroma_siting.offsets()
+                    } else {
+                        (0.0, 0.0)
+                    };
+
+                    let shift_x = if scale_x < 1.0 {
+                        siting_x * (crop.width / dst_window.width as f64 - 1.0)
+                    } else {
+                        0.0
+                    };
+
+                    let shift_y = if scale_y < 1.0 {
+                        siting_y * (crop.height / dst_window.height as f64 - 1.0)
+                    } else {
+                        0.0
+                    };
+
+                    let width = fir_x(src_width);
+                    let height = plane_desc.height_op.op(src_height);
+
+                    // The shifted crop box may reach outside of the plane, so pad it by repeating its edges
+                    let pad_x = shift_x.abs().ceil() as usize;
+                    let pad_y = shift_y.abs().ceil() as usize;
+
+                    let (plane, stride) = if pad_x + pad_y > 0 {
+                        let padded = pad_plane(
+                            src_plane,
+                            src_stride,
+                            width,
+                            height,
+                            plane_desc.pixel_type.size(),
+                            pad_x,
+                            pad_y,
+                        );
+
+                        let stride = (width + 2 * pad_x) * plane_desc.pixel_type.size();
 
-                    let src_height = src_plane.len() / src_stride;
-                    let dst_height = dst_plane.len() / dst_stride;
+                        (Cow::Owned(padded), stride)
+                    } else {
+                        (Cow::Borrowed(src_plane), src_stride)
+                    };
+
+                    let cropping = if src_crop.is_some() || pad_x + pad_y > 0 {
+                        fir::SrcCropping::Crop(fir::CropBox {
+                            left: crop.x * scale_x + shift_x + pad_x as f64,
+                            top: crop.y * scale_y + shift_y + pad_y as f64,
+                            width: crop.width * scale_x,
+                            height: crop.height * scale_y,
+                        })
+                    } else {
+                        fir::SrcCropping::None
+                    };
 
                     let src = FirAdapterIntoImageView {
                         pixel_type: plane_desc.pixel_type,
-                        width: src_fir_width,
-                        height: src_height,
-                        stride: src_stride,
-                        plane: src_plane,
+                        width: width + 2 * pad_x,
+                        height: height + 2 * pad_y,
+                        stride,
+                        plane,
                     };
 
                     let mut dst = FirAdapterIntoImageView {
                         pixel_type: plane_desc.pixel_type,
-                        width: dst_fir_width,
-                        height: dst_height,
+                        width: fir_x(dst_window.width),
+                        height: plane_desc.height_op.op(dst_window.height),
                         stride: dst_stride,
                         plane: dst_plane,
                     };
@@ -103,8 +523,8 @@ impl Resizer {
                             &mut dst,
                             Some(&fir::ResizeOptions {
                                 algorithm: alg,
-                                cropping: fir::SrcCropping::None,
-                                mul_div_alpha: false,
+                                cropping,
+                                mul_div_alpha,
                             }),
                         )
                         .expect(
@@ -114,8 +534,353 @@ impl Resizer {
             }
         });
 
+        if let (true, Some(alpha_index)) = (unpremultiply_dst, alpha_index) {
+            let (plane, stride) = &mut dst_planes[0];
+
+            unpremultiply_rgba8(
+                plane,
+                *stride,
+                dst_window.width,
+                dst_window.height,
+                alpha_index,
+            );
+        }
+
         Ok(())
     }
+
+    /// Resize in linear light through a temporary linear RGBA image with f32 components
+    fn resize_linear(
+        &mut self,
+        src: &dyn ImageRef,
+        src_crop: Option<SourceRect>,
+        dst: &mut dyn ImageMut,
+        dst_window: Window,
+    ) -> Result<(), ResizeError> {
+        let src_color = src.color();
+        let dst_color = dst.color();
+
+        let mut linear_src = fir::images::Image::new(
+            src.width() as u32,
+            src.height() as u32,
+            fir::PixelType::F32x4,
+        );
+
+        visit(
+            src.width(),
+            src.height(),
+            LinearizeVisitor {
+                reader: read_any_to_rgba(src).map_err(convert_error)?,
+                transfer: src_color.transfer(),
+                width: src.width(),
+                dst: as_f32_mut(linear_src.buffer_mut()),
+            },
+        );
+
+        let mut linear_dst = fir::images::Image::new(
+            dst_window.width as u32,
+            dst_window.height as u32,
+            fir::PixelType::F32x4,
+        );
+
+        let cropping = match src_crop {
+            Some(crop) => fir::SrcCropping::Crop(fir::CropBox {
+                left: crop.x,
+                top: crop.y,
+                width: crop.width,
+                height: crop.height,
+            }),
+            None => fir::SrcCropping::None,
+        };
+
+        // The reader always returns straight alpha, also for premultiplied images, so fir has to premultiply before
+        // and unpremultiply after filtering. The writer premultiplies again if dst is premultiplied.
+        let mul_div_alpha = src.format().has_alpha();
+
+        if self.fir.is_empty() {
+            self.fir.push(fir::Resizer::new());
+        }
+
+        self.fir[0]
+            .resize(
+                &linear_src,
+                &mut linear_dst,
+                Some(&fir::ResizeOptions {
+                    algorithm: self.alg,
+                    cropping,
+                    mul_div_alpha,
+                }),
+            )
+            .expect("Pixel type must be assured to be the same before calling fir's resize");
+
+        let linear_dst = as_f32(linear_dst.buffer());
+
+        // Encode with the source's transfer, the linear values are in its scale (e.g. cd/m² for PQ), then convert
+        // to the transfer & primaries of dst like any other conversion
+        let mut reader: Box<dyn DynRgbaReader + '_> = Box::new(EncodeSrc {
+            src: linear_dst,
+            transfer: src_color.transfer(),
+            width: dst_window.width,
+        });
+
+        if need_transfer_and_primaries_convert(&src_color, &dst_color) {
+            reader = Box::new(TransferAndPrimariesConvert::new(
+                &src_color,
+                &dst_color,
+                &ConvertOptions::default(),
+                reader,
+            ));
+        }
+
+        if dst_window.x == 0
+            && dst_window.y == 0
+            && dst_window.width == dst.width()
+            && dst_window.height == dst.height()
+        {
+            return rgba_to_any(dst, reader).map_err(convert_error);
+        }
+
+        // Write the window into a temporary image and copy it over
+        let mut window = Image::blank(dst.format(), dst_window.width, dst_window.height, dst_color);
+        rgba_to_any(&mut window, reader).map_err(convert_error)?;
+
+        for ((plane_desc, (src_plane, src_stride)), (dst_plane, dst_stride)) in dst
+            .format()
+            .plane_desc()
+            .iter()
+            .zip(window.planes())
+            .zip(dst.planes_mut())
+        {
+            let offset = plane_desc.height_op.op(dst_window.y) * dst_stride
+                + plane_desc.width_op.op(dst_window.x) * plane_desc.bytes_per_primitive;
+
+            let row_len = plane_desc.packed_stride(dst_window.width);
+
+            for y in 0..plane_desc.height_op.op(dst_window.height) {
+                dst_plane[offset + y * dst_stride..][..row_len]
+                    .copy_from_slice(&src_plane[y * src_stride..][..row_len]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn convert_error(error: ConvertError) -> ResizeError {
+    match error {
+        ConvertError::OddImageDimensions => ResizeError::OddImageDimensions,
+        ConvertError::BoundsCheck(error) => ResizeError::BoundsCheck(error),
+        ConvertError::InvalidNumberOfPlanes(error) => ResizeError::BoundsCheck(error.into()),
+        ConvertError::MismatchedImageSize | ConvertError::InvalidColorInfo => {
+            ResizeError::InvalidColorInfo
+        }
+    }
+}
+
+/// View the buffer of a fir image with f32 pixels as f32s
+fn as_f32(buffer: &[u8]) -> &[f32] {
+    // Safety: every bit pattern is a valid f32
+    let (prefix, floats, suffix) = unsafe { buffer.align_to::<f32>() };
+
+    assert!(prefix.is_empty() && suffix.is_empty());
+
+    floats
+}
+
+fn as_f32_mut(buffer: &mut [u8]) -> &mut [f32] {
+    // Safety: every bit pattern is a valid f32
+    let (prefix, floats, suffix) = unsafe { buffer.align_to_mut::<f32>() };
+
+    assert!(prefix.is_empty() && suffix.is_empty());
+
+    floats
+}
+
+/// Writes the linear RGBA values of an image into an interleaved f32 buffer
+struct LinearizeVisitor<'a, 'b> {
+    reader: Box<dyn DynRgbaReader + 'a>,
+    transfer: ColorTransfer,
+    width: usize,
+    dst: &'b mut [f32],
+}
+
+impl Image2x2Visitor for LinearizeVisitor<'_, '_> {
+    #[inline(always)]
+    unsafe fn visit<V: Vector>(&mut self, x: usize, y: usize) {
+        let mut block = self.reader.read::<V>(x, y);
+
+        self.transfer.scaled_to_linear_v(&mut [
+            &mut block.px00.r,
+            &mut block.px00.g,
+            &mut block.px00.b,
+            &mut block.px01.r,
+            &mut block.px01.g,
+            &mut block.px01.b,
+            &mut block.px10.r,
+            &mut block.px10.g,
+            &mut block.px10.b,
+            &mut block.px11.r,
+            &mut block.px11.g,
+            &mut block.px11.b,
+        ]);
+
+        let pixels = [
+            (block.px00, x, y),
+            (block.px01, x + V::LEN, y),
+            (block.px10, x, y + 1),
+            (block.px11, x + V::LEN, y + 1),
+        ];
+
+        for (px, x, y) in pixels {
+            let mut channels = [[0.0f32; 16]; 4];
+
+            for (channel, v) in channels.iter_mut().zip([px.r, px.g, px.b, px.a]) {
+                v.store(channel.as_mut_ptr());
+            }
+
+            let dst = &mut self.dst[(y * self.width + x) * 4..][..V::LEN * 4];
+
+            for (i, px) in dst.chunks_exact_mut(4).enumerate() {
+                for (c, channel) in px.iter_mut().zip(&channels) {
+                    *c = channel[i];
+                }
+            }
+        }
+    }
+}
+
+/// Reads linear RGBA values from an interleaved f32 buffer and encodes them with a transfer function
+struct EncodeSrc<'a> {
+    src: &'a [f32],
+    transfer: ColorTransfer,
+    width: usize,
+}
+
+impl EncodeSrc<'_> {
+    #[inline(always)]
+    unsafe fn read_pixels<V: Vector>(&self, x: usize, y: usize) -> RgbaPixel<V> {
+        let base = self.src[(y * self.width + x) * 4..][..V::LEN * 4].as_ptr();
+        let indices = V::lane_indices().vmulf(4.0);
+
+        RgbaPixel {
+            r: V::gather(base, indices),
+            g: V::gather(base, indices.vaddf(1.0)),
+            b: V::gather(base, indices.vaddf(2.0)),
+            a: V::gather(base, indices.vaddf(3.0)),
+        }
+    }
+}
+
+impl RgbaSrc for EncodeSrc<'_> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let mut block = RgbaBlock {
+            px00: self.read_pixels(x, y),
+            px01: self.read_pixels(x + V::LEN, y),
+            px10: self.read_pixels(x, y + 1),
+            px11: self.read_pixels(x + V::LEN, y + 1),
+        };
+
+        self.transfer.linear_to_scaled_v(&mut [
+            &mut block.px00.r,
+            &mut block.px00.g,
+            &mut block.px00.b,
+            &mut block.px01.r,
+            &mut block.px01.g,
+            &mut block.px01.b,
+            &mut block.px10.r,
+            &mut block.px10.g,
+            &mut block.px10.b,
+            &mut block.px11.r,
+            &mut block.px11.g,
+            &mut block.px11.b,
+        ]);
+
+        block
+    }
+}
+
+/// Copy a plane of `width` x `height` pixels of `pixel_size` bytes, adding `pad_x` columns and `pad_y` rows on every
+/// side which repeat the plane's edges
+fn pad_plane(
+    plane: &[u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    pixel_size: usize,
+    pad_x: usize,
+    pad_y: usize,
+) -> Vec<u8> {
+    let row_len = width * pixel_size;
+    let padded_row_len = row_len + 2 * pad_x * pixel_size;
+
+    let mut padded = Vec::with_capacity(padded_row_len * (height + 2 * pad_y));
+
+    for y in 0..height + 2 * pad_y {
+        let y = y.saturating_sub(pad_y).min(height - 1);
+        let row = &plane[y * stride..][..row_len];
+
+        for _ in 0..pad_x {
+            padded.extend_from_slice(&row[..pixel_size]);
+        }
+
+        padded.extend_from_slice(row);
+
+        for _ in 0..pad_x {
+            padded.extend_from_slice(&row[row_len - pixel_size..]);
+        }
+    }
+
+    padded
+}
+
+/// Round `value` to the nearest multiple of `alignment`
+fn align(value: f64, alignment: usize) -> usize {
+    (value / alignment as f64).round() as usize * alignment
+}
+
+fn premultiply_rgba8(
+    plane: &mut [u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    alpha_index: usize,
+) {
+    for row in plane.chunks_mut(stride).take(height) {
+        for px in row[..width * 4].chunks_exact_mut(4) {
+            let a = u32::from(px[alpha_index]);
+
+            for (i, c) in px.iter_mut().enumerate() {
+                if i != alpha_index {
+                    *c = ((u32::from(*c) * a + 127) / 255) as u8;
+                }
+            }
+        }
+    }
+}
+
+fn unpremultiply_rgba8(
+    plane: &mut [u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    alpha_index: usize,
+) {
+    for row in plane.chunks_mut(stride).take(height) {
+        for px in row[..width * 4].chunks_exact_mut(4) {
+            let a = u32::from(px[alpha_index]);
+
+            for (i, c) in px.iter_mut().enumerate() {
+                if i == alpha_index {
+                    continue;
+                }
+
+                *c = (u32::from(*c) * 255 + a / 2)
+                    .checked_div(a)
+                    .map_or(0, |c| c.min(255) as u8);
+            }
+        }
+    }
 }
 
 #[cfg(not(feature = "multi-thread"))]
@@ -209,7 +974,8 @@ unsafe impl<T: AsRef<[u8]> + Send + Sync, P: InnerPixel> fir::ImageView
     fn iter_rows(&self, start_row: u32) -> impl Iterator<Item = &[Self::Pixel]> {
         self.plane
             .as_ref()
-            .chunks_exact(self.stride)
+            .chunks(self.stride)
+            .take(self.height)
             .skip(start_row as usize)
             .map(|row| {
                 let row = &row[..self.width * P::pixel_type().size()];
@@ -231,7 +997,8 @@ unsafe impl<T: AsRef<[u8]> + AsMut<[u8]> + Send + Sync, P: InnerPixel> fir::Imag
     fn iter_rows_mut(&mut self, start_row: u32) -> impl Iterator<Item = &mut [Self::Pixel]> {
         self.plane
             .as_mut()
-            .chunks_exact_mut(self.stride)
+            .chunks_mut(self.stride)
+            .take(self.height)
             .skip(start_row as usize)
             .map(|row| {
                 let row = &mut row[..self.width * P::pixel_type().size()];
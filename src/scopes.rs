@@ -0,0 +1,464 @@
+//! Broadcast scopes: waveform, vectorscope and false color
+
+use crate::formats::DynRgbaReader;
+use crate::formats::max_value_for_bits;
+use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::formats::visit_2x2::{Image2x2Visitor, visit};
+use crate::vector::Vector;
+use crate::{
+    ColorSpace, ColorTransfer, ConvertError, ImageMut, ImageRef, read_any_to_rgba, rgba_to_any,
+};
+
+/// Errors that may occur when rendering a scope
+#[derive(Debug, thiserror::Error)]
+pub enum ScopeError {
+    #[error("scope destination has a width or height of zero")]
+    EmptyDestination,
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+}
+
+/// Components shown by [`waveform`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveformMode {
+    /// Luma (Y') of the full image in white
+    Luma,
+    /// R', G' and B' side by side, each in a third of the destination
+    RgbParade,
+}
+
+/// Luma range highlighted by [`false_color`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FalseColorZone {
+    /// Upper (exclusive) luma bound of the zone, the lower bound is the upper bound of the previous zone
+    pub max: f32,
+    /// R'G'B' color of the zone, `None` to show the pixel's luma as gray
+    pub color: Option<[f32; 3]>,
+}
+
+/// Default false color zones, from crushed blacks (purple) over middle gray (green) and skin tones (pink) to clipped
+/// highlights (red)
+pub const DEFAULT_FALSE_COLOR_ZONES: &[FalseColorZone] = &[
+    FalseColorZone {
+        max: 0.025,
+        color: Some([0.5, 0.0, 0.5]),
+    },
+    FalseColorZone {
+        max: 0.1,
+        color: Some([0.0, 0.0, 1.0]),
+    },
+    FalseColorZone {
+        max: 0.38,
+        color: None,
+    },
+    FalseColorZone {
+        max: 0.44,
+        color: Some([0.0, 0.8, 0.0]),
+    },
+    FalseColorZone {
+        max: 0.52,
+        color: None,
+    },
+    FalseColorZone {
+        max: 0.56,
+        color: Some([1.0, 0.5, 0.6]),
+    },
+    FalseColorZone {
+        max: 0.97,
+        color: None,
+    },
+    FalseColorZone {
+        max: 0.99,
+        color: Some([1.0, 1.0, 0.0]),
+    },
+    FalseColorZone {
+        max: f32::INFINITY,
+        color: Some([1.0, 0.0, 0.0]),
+    },
+];
+
+/// Render a waveform of `src` into `dst`
+///
+/// Columns of `dst` map to the columns of `src`, rows to signal levels from 0% (bottom) to 100% (top), out of range
+/// levels are drawn at the edges. The brightness of a point increases with the number of pixels with that level. Luma
+/// is computed from the R'G'B' values of `src` using `space`, the same way converting to a YUV format does.
+///
+/// All images must have dimensions divisible by 2 and `dst` must not be empty.
+pub fn waveform(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    space: ColorSpace,
+    mode: WaveformMode,
+) -> Result<(), ScopeError> {
+    check_dimensions(src)?;
+    check_scope_dimensions(dst)?;
+
+    let (src_width, dst_width, dst_height) = (src.width(), dst.width(), dst.height());
+
+    let (sections, colors): (usize, &[[f32; 3]]) = match mode {
+        WaveformMode::Luma => (1, &[[1.0, 1.0, 1.0]]),
+        WaveformMode::RgbParade => (3, &[[1.0, 0.2, 0.2], [0.2, 1.0, 0.2], [0.3, 0.3, 1.0]]),
+    };
+
+    let section_width = dst_width / sections;
+
+    let mut grid = ScopeGrid::new(dst_width, dst_height);
+
+    sample(src, space, |x, _, px| {
+        let values = match mode {
+            WaveformMode::Luma => &px.yuv[..1],
+            WaveformMode::RgbParade => &px.rgb[..],
+        };
+
+        // Every pixel covers at least one column, or multiple if dst is wider than src
+        let start = x * section_width / src_width;
+        let end = ((x + 1) * section_width / src_width).max(start + 1);
+
+        for (section, value) in values.iter().enumerate() {
+            let row = level_to_row(*value, dst_height);
+
+            for column in start..end {
+                grid.hit(section * section_width + column, row, section);
+            }
+        }
+    })?;
+
+    Ok(grid.render(dst, colors)?)
+}
+
+/// Render a vectorscope of `src` into `dst`
+///
+/// U (Cb) is shown on the horizontal and V (Cr) on the vertical axis, with the achromatic point in the center of
+/// `dst` and the edges at ±0.5. The brightness of a point increases with the number of pixels with that color.
+/// U & V are computed from the R'G'B' values of `src` using `space`, the same way converting to a YUV format does.
+///
+/// All images must have dimensions divisible by 2 and `dst` must not be empty.
+pub fn vectorscope(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    space: ColorSpace,
+) -> Result<(), ScopeError> {
+    check_dimensions(src)?;
+    check_scope_dimensions(dst)?;
+
+    let (dst_width, dst_height) = (dst.width(), dst.height());
+
+    let mut grid = ScopeGrid::new(dst_width, dst_height);
+
+    sample(src, space, |_, _, px| {
+        let [_, u, v] = px.yuv;
+
+        let column = level_to_row(0.5 - u, dst_width);
+        let row = level_to_row(v + 0.5, dst_height);
+
+        grid.hit(column, row, 0);
+    })?;
+
+    Ok(grid.render(dst, &[[1.0, 1.0, 1.0]])?)
+}
+
+/// Write a false color image of `src` into `dst`, which must have the same size
+///
+/// Every pixel is colored by the [`FalseColorZone`] its luma falls into, zones must be sorted by their upper bound.
+/// Luma is computed from the R'G'B' values of `src` using `space`.
+pub fn false_color(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    space: ColorSpace,
+    zones: &[FalseColorZone],
+) -> Result<(), ScopeError> {
+    if src.width() != dst.width() || src.height() != dst.height() {
+        return Err(ConvertError::MismatchedImageSize.into());
+    }
+
+    check_dimensions(src)?;
+
+    let src_color = src.color();
+
+    // Boxed to only instantiate the luma computation once per vector type instead of once per format
+    let reader: Box<dyn DynRgbaReader + '_> = Box::new(FalseColor {
+        space,
+        rgb_to_yuv: space.rgb_to_yuv_mat(),
+        transfer: src_color.transfer(),
+        rgb_to_xyz: src_color.primaries().rgb_to_xyz_mat(),
+        max_value: max_value_for_bits(src.format().bits_per_component()),
+        zones,
+        src: read_any_to_rgba(src)?,
+    });
+
+    Ok(rgba_to_any(dst, reader)?)
+}
+
+fn check_dimensions(image: &dyn ImageRef) -> Result<(), ConvertError> {
+    if image.width() % 2 == 1 || image.height() % 2 == 1 {
+        Err(ConvertError::OddImageDimensions)
+    } else {
+        Ok(())
+    }
+}
+
+/// Scopes map levels to rows and columns of `dst`, which requires at least one of each
+fn check_scope_dimensions(dst: &dyn ImageRef) -> Result<(), ScopeError> {
+    check_dimensions(dst)?;
+
+    if dst.width() == 0 || dst.height() == 0 {
+        return Err(ScopeError::EmptyDestination);
+    }
+
+    Ok(())
+}
+
+/// Map a level from 0.0 (bottom) to 1.0 (top) to a row of a scope with the given height
+fn level_to_row(level: f32, height: usize) -> usize {
+    let row = ((1.0 - level) * (height - 1) as f32).round();
+
+    // Negative values and NaN saturate to 0
+    (row as usize).min(height - 1)
+}
+
+/// Values of a single pixel
+struct Sample {
+    rgb: [f32; 3],
+    yuv: [f32; 3],
+}
+
+/// Call `f` with the position, R'G'B' and Y'UV values of every pixel of `src`
+fn sample(
+    src: &dyn ImageRef,
+    space: ColorSpace,
+    f: impl FnMut(usize, usize, Sample),
+) -> Result<(), ConvertError> {
+    let color = src.color();
+
+    visit(
+        src.width(),
+        src.height(),
+        SampleVisitor {
+            reader: read_any_to_rgba(src)?,
+            space,
+            rgb_to_yuv: space.rgb_to_yuv_mat(),
+            transfer: color.transfer(),
+            rgb_to_xyz: color.primaries().rgb_to_xyz_mat(),
+            max_value: max_value_for_bits(src.format().bits_per_component()),
+            f,
+        },
+    );
+
+    Ok(())
+}
+
+struct SampleVisitor<'a, F> {
+    reader: Box<dyn DynRgbaReader + 'a>,
+
+    space: ColorSpace,
+    rgb_to_yuv: [[f32; 3]; 3],
+    transfer: ColorTransfer,
+    rgb_to_xyz: &'static [[f32; 3]; 3],
+    max_value: f32,
+
+    f: F,
+}
+
+impl<F: FnMut(usize, usize, Sample)> Image2x2Visitor for SampleVisitor<'_, F> {
+    #[inline(always)]
+    unsafe fn visit<V: Vector>(&mut self, x: usize, y: usize) {
+        let block = self.reader.read::<V>(x, y);
+
+        for (px, x, y) in [
+            (block.px00, x, y),
+            (block.px01, x + V::LEN, y),
+            (block.px10, x, y + 1),
+            (block.px11, x + V::LEN, y + 1),
+        ] {
+            let (y_, u, v) = self.space.rgb_to_yuv(
+                &self.rgb_to_yuv,
+                self.transfer,
+                self.rgb_to_xyz,
+                self.max_value,
+                px.r,
+                px.g,
+                px.b,
+            );
+
+            let mut values = [[0.0f32; 16]; 6];
+
+            for (v, values) in [px.r, px.g, px.b, y_, u, v].into_iter().zip(&mut values) {
+                v.store(values.as_mut_ptr());
+            }
+
+            for i in 0..V::LEN {
+                let [r, g, b, y_, u, v] = values.map(|values| values[i]);
+
+                (self.f)(
+                    x + i,
+                    y,
+                    Sample {
+                        rgb: [r, g, b],
+                        yuv: [y_, u, v],
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Number of hits per scope position, with the color of every position
+struct ScopeGrid {
+    width: usize,
+    hits: Vec<u32>,
+    /// Index into the colors passed to `render`
+    color: Vec<u8>,
+    total: u64,
+}
+
+impl ScopeGrid {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            hits: vec![0; width * height],
+            color: vec![0; width * height],
+            total: 0,
+        }
+    }
+
+    fn hit(&mut self, column: usize, row: usize, color: usize) {
+        if column >= self.width {
+            return;
+        }
+
+        let index = row * self.width + column;
+
+        self.hits[index] += 1;
+        self.color[index] = color as u8;
+        self.total += 1;
+    }
+
+    fn render(self, dst: &mut dyn ImageMut, colors: &[[f32; 3]]) -> Result<(), ConvertError> {
+        // Positions with the number of hits they'd get if all hits were evenly distributed are shown at ~63%
+        let expected = (self.total as f32 / self.hits.len().max(1) as f32).max(1.0);
+
+        let mut rgb = [
+            vec![0.0; self.hits.len()],
+            vec![0.0; self.hits.len()],
+            vec![0.0; self.hits.len()],
+        ];
+
+        for (i, (hits, color)) in self.hits.iter().zip(&self.color).enumerate() {
+            let intensity = 1.0 - (-(*hits as f32) / expected).exp();
+
+            for (plane, c) in rgb.iter_mut().zip(colors[usize::from(*color)]) {
+                plane[i] = intensity * c;
+            }
+        }
+
+        rgba_to_any(
+            dst,
+            PlanarRgb {
+                width: self.width,
+                rgb,
+            },
+        )
+    }
+}
+
+/// Reads from planar float R'G'B' buffers
+struct PlanarRgb {
+    width: usize,
+    rgb: [Vec<f32>; 3],
+}
+
+impl PlanarRgb {
+    #[inline(always)]
+    unsafe fn read_pixels<V: Vector>(&self, x: usize, y: usize) -> RgbaPixel<V> {
+        let offset = y * self.width + x;
+
+        debug_assert!(offset + V::LEN <= self.rgb[0].len());
+
+        let indices = V::lane_indices();
+        let [r, g, b] = &self.rgb;
+
+        RgbaPixel {
+            r: V::gather(r.as_ptr().add(offset), indices),
+            g: V::gather(g.as_ptr().add(offset), indices),
+            b: V::gather(b.as_ptr().add(offset), indices),
+            a: V::splat(1.0),
+        }
+    }
+}
+
+impl RgbaSrc for PlanarRgb {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        RgbaBlock {
+            px00: self.read_pixels(x, y),
+            px01: self.read_pixels(x + V::LEN, y),
+            px10: self.read_pixels(x, y + 1),
+            px11: self.read_pixels(x + V::LEN, y + 1),
+        }
+    }
+}
+
+struct FalseColor<'a, S> {
+    space: ColorSpace,
+    rgb_to_yuv: [[f32; 3]; 3],
+    transfer: ColorTransfer,
+    rgb_to_xyz: &'static [[f32; 3]; 3],
+    max_value: f32,
+
+    zones: &'a [FalseColorZone],
+
+    src: S,
+}
+
+impl<S> FalseColor<'_, S> {
+    #[inline(always)]
+    unsafe fn false_color<V: Vector>(&self, px: RgbaPixel<V>) -> RgbaPixel<V> {
+        let (luma, _, _) = self.space.rgb_to_yuv(
+            &self.rgb_to_yuv,
+            self.transfer,
+            self.rgb_to_xyz,
+            self.max_value,
+            px.r,
+            px.g,
+            px.b,
+        );
+
+        let mut rgb = [luma; 3];
+
+        // Apply the zones from the last to the first, so that the first zone containing the value wins
+        for zone in self.zones.iter().rev() {
+            let inside = luma.ltf(zone.max);
+
+            let color = match zone.color {
+                Some(color) => color.map(|c| V::splat(c)),
+                None => [luma; 3],
+            };
+
+            rgb = [0, 1, 2].map(|i| V::select(color[i], rgb[i], inside));
+        }
+
+        let [r, g, b] = rgb;
+
+        RgbaPixel {
+            r,
+            g,
+            b,
+            a: V::splat(1.0),
+        }
+    }
+}
+
+impl<S: RgbaSrc> RgbaSrc for FalseColor<'_, S> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        let block = self.src.read::<V>(x, y);
+
+        RgbaBlock {
+            px00: self.false_color(block.px00),
+            px01: self.false_color(block.px01),
+            px10: self.false_color(block.px10),
+            px11: self.false_color(block.px11),
+        }
+    }
+}
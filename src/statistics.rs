@@ -0,0 +1,357 @@
+//! Per channel statistics and histograms of images
+
+use crate::crop::check_window;
+use crate::formats::DynRgbaReader;
+use crate::formats::rgb::RgbaSrc;
+use crate::formats::visit_2x2::{Image2x2Visitor, visit};
+use crate::plane_decs::{Op, plane_layouts};
+use crate::vector::Vector;
+use crate::{BoundsCheckError, ConvertError, ImageRef, ImageRefExt, Window, read_any_to_rgba};
+use std::ops::Range;
+
+/// Errors that may occur when computing statistics
+#[derive(Debug, thiserror::Error)]
+pub enum StatisticsError {
+    #[error("the given window goes out of the image's bounds")]
+    WindowOutOfBounds,
+
+    #[error(transparent)]
+    BoundsCheck(#[from] BoundsCheckError),
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+}
+
+/// A color channel of an image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Channel {
+    Y,
+    U,
+    V,
+    R,
+    G,
+    B,
+    A,
+}
+
+/// Statistics of a single channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelStatistics {
+    pub channel: Channel,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f64,
+    /// Number of samples per bin, the bins evenly divide the channel's range
+    pub histogram: Vec<u64>,
+}
+
+/// Compute statistics of every channel using the code values of the image's format, e.g. `0..=1023` for 10-bit
+/// formats.
+///
+/// Channels are returned in the order Y, U, V or R, G, B, A. The histograms have one bin per code value. Subsampled
+/// chroma samples are included if they belong to any pixel inside the `window`. Only the histograms are built from
+/// the samples, min, max & mean are derived from them.
+pub fn native_statistics(
+    image: &dyn ImageRef,
+    window: Option<Window>,
+) -> Result<Vec<ChannelStatistics>, StatisticsError> {
+    image.bounds_check()?;
+
+    let format = image.format();
+    let window = check_window(image.width(), image.height(), window)
+        .ok_or(StatisticsError::WindowOutOfBounds)?;
+
+    let layouts = plane_layouts(format);
+
+    let mut channels: Vec<Channel> = layouts
+        .iter()
+        .flat_map(|layout| layout.primitives.iter().map(|(channel, ..)| *channel))
+        .collect();
+
+    channels.sort();
+    channels.dedup();
+
+    let bins = 1 << format.bits_per_component();
+    let mut histograms = vec![vec![0u64; bins]; channels.len()];
+
+    for (((plane, stride), desc), layout) in
+        image.planes().zip(format.plane_desc()).zip(layouts.iter())
+    {
+        let rows = match desc.height_op {
+            Op::Div(n) => window.y / n..(window.y + window.height).div_ceil(n),
+            _ => window.y..window.y + window.height,
+        };
+
+        let group_len = layout.primitives.len();
+
+        // Index into the histograms for every primitive of a group
+        let slots: Vec<usize> = layout
+            .primitives
+            .iter()
+            .map(|(channel, ..)| channels.binary_search(channel).expect("channel is listed"))
+            .collect();
+
+        // Groups of every primitive with at least one pixel inside the window
+        let groups: Vec<_> = layout
+            .primitives
+            .iter()
+            .map(|&(_, start, end)| {
+                let first = (window.x + 1).saturating_sub(end).div_ceil(layout.width);
+                let last = (window.x + window.width)
+                    .saturating_sub(start)
+                    .div_ceil(layout.width);
+
+                first..last
+            })
+            .collect();
+
+        for row in rows {
+            let row = &plane[row * stride..];
+
+            for (i, groups) in groups.iter().enumerate() {
+                let histogram = &mut histograms[slots[i]];
+
+                for group in groups.clone() {
+                    let index = group * group_len + i;
+
+                    let value = match desc.bytes_per_primitive {
+                        1 => usize::from(row[index]),
+                        _ => usize::from(u16::from_ne_bytes([row[index * 2], row[index * 2 + 1]])),
+                    };
+
+                    histogram[value.min(bins - 1)] += 1;
+                }
+            }
+        }
+    }
+
+    let statistics = channels
+        .into_iter()
+        .zip(histograms)
+        .map(|(channel, histogram)| {
+            let count: u64 = histogram.iter().sum();
+            let sum: f64 = histogram
+                .iter()
+                .enumerate()
+                .map(|(value, n)| value as f64 * *n as f64)
+                .sum();
+
+            let min = histogram.iter().position(|n| *n > 0).unwrap_or(0);
+            let max = histogram.iter().rposition(|n| *n > 0).unwrap_or(0);
+
+            ChannelStatistics {
+                channel,
+                min: min as f32,
+                max: max as f32,
+                mean: sum / count as f64,
+                histogram,
+            }
+        })
+        .collect();
+
+    Ok(statistics)
+}
+
+/// Compute statistics of the image's R', G' and B' channels and its luma (Y') in the image's color, with values
+/// normalized to `0.0..=1.0`.
+///
+/// Channels are returned in the order R, G, B, Y. Luma is computed using the luminance coefficients of the image's
+/// primaries. The histograms have `bins` (at least 1) bins, values outside of `0.0..=1.0` are counted in the first or
+/// last bin. The image must have dimensions divisible by 2.
+///
+/// Min, max and mean are accumulated per SIMD lane and reduced at the end, only the histogram bins are counted one
+/// value at a time.
+pub fn rgb_statistics(
+    image: &dyn ImageRef,
+    window: Option<Window>,
+    bins: usize,
+) -> Result<Vec<ChannelStatistics>, StatisticsError> {
+    if image.width() % 2 == 1 || image.height() % 2 == 1 {
+        return Err(ConvertError::OddImageDimensions.into());
+    }
+
+    let window = check_window(image.width(), image.height(), window)
+        .ok_or(StatisticsError::WindowOutOfBounds)?;
+    let reader = read_any_to_rgba(image)?;
+
+    let m = image.color().primaries().rgb_to_xyz_mat();
+
+    let mut accumulators: [Accumulator; 4] = std::array::from_fn(|_| Accumulator::new(bins.max(1)));
+
+    // Read whole 2x2 blocks, pixels outside of the window are skipped when accumulating
+    let x = window.x & !1;
+    let y = window.y & !1;
+    let width = (window.x + window.width).next_multiple_of(2) - x;
+    let height = (window.y + window.height).next_multiple_of(2) - y;
+
+    visit(
+        width,
+        height,
+        RgbStatisticsVisitor {
+            reader,
+            x,
+            y,
+            window,
+            luma_weights: [m[0][1], m[1][1], m[2][1]],
+            accumulators: &mut accumulators,
+        },
+    );
+
+    let statistics = [Channel::R, Channel::G, Channel::B, Channel::Y]
+        .into_iter()
+        .zip(accumulators)
+        .map(|(channel, accumulator)| accumulator.finish(channel))
+        .collect();
+
+    Ok(statistics)
+}
+
+/// Largest number of lanes of any [`Vector`] implementation
+const MAX_LANES: usize = 16;
+
+struct Accumulator {
+    /// Per lane min, max & sum, reduced when finished
+    min: [f32; MAX_LANES],
+    max: [f32; MAX_LANES],
+    row_sum: [f32; MAX_LANES],
+
+    /// The per lane sums are flushed into this after every row, so they never add up enough values to lose precision
+    sum: f64,
+    count: u64,
+    histogram: Vec<u64>,
+}
+
+impl Accumulator {
+    fn new(bins: usize) -> Self {
+        Self {
+            min: [f32::INFINITY; MAX_LANES],
+            max: [f32::NEG_INFINITY; MAX_LANES],
+            row_sum: [0.0; MAX_LANES],
+            sum: 0.0,
+            count: 0,
+            histogram: vec![0; bins],
+        }
+    }
+
+    /// Add the given `lanes` of `values`, all other lanes are ignored
+    #[inline(always)]
+    unsafe fn add<V: Vector>(&mut self, values: V, lanes: Range<usize>) {
+        let (min, max, sum) = if lanes.len() == V::LEN {
+            (values, values, values)
+        } else {
+            let lane = V::lane_indices();
+            let (start, end) = (lanes.start as f32, lanes.end as f32);
+
+            (
+                mask_lanes(values, lane, start, end, f32::INFINITY),
+                mask_lanes(values, lane, start, end, f32::NEG_INFINITY),
+                mask_lanes(values, lane, start, end, 0.0),
+            )
+        };
+
+        V::load(self.min.as_ptr())
+            .vmin(min)
+            .store(self.min.as_mut_ptr());
+        V::load(self.max.as_ptr())
+            .vmax(max)
+            .store(self.max.as_mut_ptr());
+        V::load(self.row_sum.as_ptr())
+            .vadd(sum)
+            .store(self.row_sum.as_mut_ptr());
+        self.count += lanes.len() as u64;
+
+        let bins = self.histogram.len();
+
+        let mut scaled = [0.0f32; MAX_LANES];
+        values.vmulf(bins as f32).store(scaled.as_mut_ptr());
+
+        for scaled in &scaled[lanes] {
+            // Negative values and NaN saturate to 0
+            let bin = (*scaled as usize).min(bins - 1);
+            self.histogram[bin] += 1;
+        }
+    }
+
+    fn flush_row(&mut self) {
+        self.sum += self.row_sum.iter().copied().map(f64::from).sum::<f64>();
+        self.row_sum = [0.0; MAX_LANES];
+    }
+
+    fn finish(mut self, channel: Channel) -> ChannelStatistics {
+        self.flush_row();
+
+        ChannelStatistics {
+            channel,
+            min: self.min.into_iter().fold(f32::INFINITY, f32::min),
+            max: self.max.into_iter().fold(f32::NEG_INFINITY, f32::max),
+            mean: self.sum / self.count as f64,
+            histogram: self.histogram,
+        }
+    }
+}
+
+/// Replace the lanes of `values` whose index in `lane` is outside of `start..end` with `outside`
+#[inline(always)]
+unsafe fn mask_lanes<V: Vector>(values: V, lane: V, start: f32, end: f32, outside: f32) -> V {
+    let outside = V::splat(outside);
+    let values = V::select(outside, values, lane.ltf(start));
+
+    V::select(values, outside, lane.ltf(end))
+}
+
+struct RgbStatisticsVisitor<'a, 'b> {
+    reader: Box<dyn DynRgbaReader + 'a>,
+
+    /// Offset of the visited area inside the image
+    x: usize,
+    y: usize,
+
+    window: Window,
+    luma_weights: [f32; 3],
+
+    accumulators: &'b mut [Accumulator; 4],
+}
+
+impl Image2x2Visitor for RgbStatisticsVisitor<'_, '_> {
+    #[inline(always)]
+    unsafe fn visit<V: Vector>(&mut self, x: usize, y: usize) {
+        if x == 0 {
+            for accumulator in self.accumulators.iter_mut() {
+                accumulator.flush_row();
+            }
+        }
+
+        let x = self.x + x;
+        let y = self.y + y;
+
+        let block = self.reader.read::<V>(x, y);
+
+        let [wr, wg, wb] = self.luma_weights;
+        let window = self.window;
+
+        for (px, x, y) in [
+            (block.px00, x, y),
+            (block.px01, x + V::LEN, y),
+            (block.px10, x, y + 1),
+            (block.px11, x + V::LEN, y + 1),
+        ] {
+            if y < window.y || y >= window.y + window.height {
+                continue;
+            }
+
+            // Lanes of the vector inside the window
+            let lanes = window.x.saturating_sub(x).min(V::LEN)
+                ..(window.x + window.width).saturating_sub(x).min(V::LEN);
+
+            if lanes.is_empty() {
+                continue;
+            }
+
+            let luma = px.r.vmulf(wr).vadd(px.g.vmulf(wg)).vadd(px.b.vmulf(wb));
+
+            for (accumulator, v) in self.accumulators.iter_mut().zip([px.r, px.g, px.b, luma]) {
+                accumulator.add(v, lanes.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::formats::DynRgbaReader;
+use crate::formats::rgb::{RgbaBlock, RgbaPixel, RgbaSrc};
+use crate::vector::Vector;
+use crate::{
+    ConvertError, ConvertOptions, ImageMut, ImageRef, ImageRefExt, PixelFormat,
+    TransferAndPrimariesConvert, need_transfer_and_primaries_convert, read_any_to_rgba,
+    rgba_to_any,
+};
+
+/// Rotation or mirroring of an image, used with [`transform`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Rotate by 90 degrees clockwise
+    Rotate90,
+    /// Rotate by 180 degrees
+    Rotate180,
+    /// Rotate by 270 degrees clockwise (90 degrees counterclockwise)
+    Rotate270,
+    /// Mirror along the vertical axis, swapping left and right
+    FlipHorizontal,
+    /// Mirror along the horizontal axis, swapping top and bottom
+    FlipVertical,
+    /// Mirror along the diagonal from the top left to the bottom right corner
+    Transpose,
+    /// Mirror along the diagonal from the top right to the bottom left corner
+    Transverse,
+}
+
+impl Transform {
+    /// Returns if the transform swaps width and height of the image
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::Transverse
+        )
+    }
+
+    /// Dimensions of the transformed image
+    pub fn dimensions(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swaps_dimensions() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Returns if the transform reverses the order of pixels inside a row of the source image
+    fn mirrors_rows(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate180
+                | Transform::Rotate270
+                | Transform::FlipHorizontal
+                | Transform::Transverse
+        )
+    }
+
+    /// Map a position in the transformed image of the given (transformed) size to the position in the source image
+    fn source(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Rotate90 => (y, width - 1 - x),
+            Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+            Transform::Rotate270 => (height - 1 - y, x),
+            Transform::FlipHorizontal => (width - 1 - x, y),
+            Transform::FlipVertical => (x, height - 1 - y),
+            Transform::Transpose => (y, x),
+            Transform::Transverse => (height - 1 - y, width - 1 - x),
+        }
+    }
+}
+
+/// Rotate or mirror `src` into `dst`, converting pixel format and color on the way
+///
+/// The dimensions of `dst` must be the ones of `src` after the transform (see [`Transform::dimensions`]) and
+/// divisible by 2.
+///
+/// If both images share format and color and the format's planes can be transformed as is, samples are moved
+/// without any conversion. This is the case for all 4:2:0, 4:4:4 and RGB formats. Formats with horizontally
+/// subsampled chroma (4:2:2) cannot be rotated by 90 degrees in place as their chroma would become vertically
+/// subsampled, they (and [`PixelFormat::YUYV`] when mirrored horizontally) are converted in a single pass
+/// through RGB, resampling the chroma of the rotated image.
+pub fn transform(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    transform: Transform,
+) -> Result<(), ConvertError> {
+    let (width, height) = transform.dimensions(src.width(), src.height());
+
+    if width != dst.width() || height != dst.height() {
+        return Err(ConvertError::MismatchedImageSize);
+    }
+
+    if width % 2 == 1 || height % 2 == 1 {
+        return Err(ConvertError::OddImageDimensions);
+    }
+
+    if src.format() == dst.format()
+        && src.color() == dst.color()
+        && let Some(element_widths) = element_widths(src.format(), transform)
+    {
+        return transform_planes(src, dst, transform, element_widths);
+    }
+
+    let src_color = src.color();
+    let dst_color = dst.color();
+
+    let reader: Box<dyn DynRgbaReader + '_> = Box::new(TransformSrc {
+        reader: read_any_to_rgba(src)?,
+        transform,
+        width,
+        height,
+    });
+
+    if need_transfer_and_primaries_convert(&src_color, &dst_color) {
+        let reader = TransferAndPrimariesConvert::new(
+            &src_color,
+            &dst_color,
+            &ConvertOptions::default(),
+            reader,
+        );
+
+        rgba_to_any(dst, reader)
+    } else {
+        rgba_to_any(dst, reader)
+    }
+}
+
+/// Width in pixels of the elements of every plane, elements are moved as a whole when transforming planes directly
+///
+/// Returns `None` if the format cannot be transformed without converting it.
+fn element_widths(format: PixelFormat, transform: Transform) -> Option<&'static [usize]> {
+    use PixelFormat::*;
+
+    const SUBSAMPLED: &[usize] = &[1, 2, 2];
+    const FULL_444: &[usize] = &[1, 1, 1];
+    const SEMI_PLANAR_420: &[usize] = &[1, 2];
+    const PACKED_422: &[usize] = &[2];
+    const INTERLEAVED: &[usize] = &[1];
+
+    // Swapping width and height turns horizontal subsampling into vertical subsampling
+    let swaps = transform.swaps_dimensions();
+
+    match format {
+        #[cfg(feature = "I420")]
+        I420 => Some(SUBSAMPLED),
+        #[cfg(feature = "I422")]
+        I422 => (!swaps).then_some(SUBSAMPLED),
+        #[cfg(feature = "I444")]
+        I444 => Some(FULL_444),
+        #[cfg(feature = "I010")]
+        I010 => Some(SUBSAMPLED),
+        #[cfg(feature = "I012")]
+        I012 => Some(SUBSAMPLED),
+        #[cfg(feature = "I210")]
+        I210 => (!swaps).then_some(SUBSAMPLED),
+        #[cfg(feature = "I212")]
+        I212 => (!swaps).then_some(SUBSAMPLED),
+        #[cfg(feature = "I410")]
+        I410 => Some(FULL_444),
+        #[cfg(feature = "I412")]
+        I412 => Some(FULL_444),
+        #[cfg(feature = "NV12")]
+        NV12 => Some(SEMI_PLANAR_420),
+        #[cfg(feature = "P010")]
+        P010 => Some(SEMI_PLANAR_420),
+        #[cfg(feature = "P012")]
+        P012 => Some(SEMI_PLANAR_420),
+        // Elements contain 2 luma samples which would have to be swapped when mirroring rows
+        #[cfg(feature = "YUYV")]
+        YUYV => (!swaps && !transform.mirrors_rows()).then_some(PACKED_422),
+        #[cfg(feature = "RGBA")]
+        RGBA => Some(INTERLEAVED),
+        #[cfg(feature = "BGRA")]
+        BGRA => Some(INTERLEAVED),
+        #[cfg(feature = "ARGB")]
+        ARGB => Some(INTERLEAVED),
+        #[cfg(feature = "ABGR")]
+        ABGR => Some(INTERLEAVED),
+        #[cfg(feature = "RGB")]
+        RGB => Some(INTERLEAVED),
+        #[cfg(feature = "BGR")]
+        BGR => Some(INTERLEAVED),
+    }
+}
+
+/// Transform every plane by moving its elements
+fn transform_planes(
+    src: &dyn ImageRef,
+    dst: &mut dyn ImageMut,
+    transform: Transform,
+    element_widths: &[usize],
+) -> Result<(), ConvertError> {
+    src.bounds_check()?;
+    dst.bounds_check()?;
+
+    let desc = src.format().plane_desc();
+    let width = dst.width();
+    let height = dst.height();
+
+    for ((desc, element_width), ((src_plane, src_stride), (dst_plane, dst_stride))) in desc
+        .iter()
+        .zip(element_widths)
+        .zip(src.planes().zip(dst.planes_mut()))
+    {
+        let element_size = desc.packed_stride(*element_width);
+
+        // Size of the plane in elements, after the transform
+        let columns = width / element_width;
+        let rows = desc.height_op.op(height);
+
+        for y in 0..rows {
+            let dst_row = &mut dst_plane[y * dst_stride..];
+
+            for x in 0..columns {
+                let (src_x, src_y) = transform.source(x, y, columns, rows);
+                let src_offset = src_y * src_stride + src_x * element_size;
+
+                dst_row[x * element_size..(x + 1) * element_size]
+                    .copy_from_slice(&src_plane[src_offset..src_offset + element_size]);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the transformed image from a reader of the source image
+///
+/// Every 2x2 block of the transformed image is a 2x2 block of the source, these are read one at a time and
+/// reordered into the vectors of the requested block.
+struct TransformSrc<'a> {
+    reader: Box<dyn DynRgbaReader + 'a>,
+    transform: Transform,
+
+    /// Dimensions of the transformed image
+    width: usize,
+    height: usize,
+}
+
+impl RgbaSrc for TransformSrc<'_> {
+    #[inline(always)]
+    unsafe fn read<V: Vector>(&mut self, x: usize, y: usize) -> RgbaBlock<V> {
+        // r, g, b, a of both rows for up to 2 * 16 pixels
+        let mut rows = [[[0.0f32; 32]; 4]; 2];
+
+        for i in (0..V::LEN * 2).step_by(2) {
+            let (x0, y0) = self.transform.source(x + i, y, self.width, self.height);
+            let (x1, y1) = self
+                .transform
+                .source(x + i + 1, y + 1, self.width, self.height);
+
+            let src_x = x0.min(x1);
+            let src_y = y0.min(y1);
+
+            let block = self.reader.read::<f32>(src_x, src_y);
+
+            for (dy, row) in rows.iter_mut().enumerate() {
+                for dx in 0..2 {
+                    let (px, py) =
+                        self.transform
+                            .source(x + i + dx, y + dy, self.width, self.height);
+
+                    let pixel = match (px - src_x, py - src_y) {
+                        (0, 0) => block.px00,
+                        (1, 0) => block.px01,
+                        (0, 1) => block.px10,
+                        _ => block.px11,
+                    };
+
+                    for (channel, value) in row.iter_mut().zip([pixel.r, pixel.g, pixel.b, pixel.a])
+                    {
+                        channel[i + dx] = value;
+                    }
+                }
+            }
+        }
+
+        let lanes = V::lane_indices();
+
+        let load = |row: &[[f32; 32]; 4], offset: usize| RgbaPixel {
+            r: V::gather(row[0][offset..].as_ptr(), lanes),
+            g: V::gather(row[1][offset..].as_ptr(), lanes),
+            b: V::gather(row[2][offset..].as_ptr(), lanes),
+            a: V::gather(row[3][offset..].as_ptr(), lanes),
+        };
+
+        RgbaBlock {
+            px00: load(&rows[0], 0),
+            px01: load(&rows[0], V::LEN),
+            px10: load(&rows[1], 0),
+            px11: load(&rows[1], V::LEN),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform;
+
+    #[test]
+    fn source_positions() {
+        // 4x2 source with the pixels numbered row by row
+        let src = [[0, 1, 2, 3], [4, 5, 6, 7]];
+
+        let transformed = |transform: Transform| {
+            let (width, height) = transform.dimensions(4, 2);
+
+            (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| {
+                            let (x, y) = transform.source(x, y, width, height);
+                            src[y][x]
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            transformed(Transform::Rotate90),
+            [[4, 0], [5, 1], [6, 2], [7, 3]]
+        );
+        assert_eq!(
+            transformed(Transform::Rotate180),
+            [[7, 6, 5, 4], [3, 2, 1, 0]]
+        );
+        assert_eq!(
+            transformed(Transform::Rotate270),
+            [[3, 7], [2, 6], [1, 5], [0, 4]]
+        );
+        assert_eq!(
+            transformed(Transform::FlipHorizontal),
+            [[3, 2, 1, 0], [7, 6, 5, 4]]
+        );
+        assert_eq!(
+            transformed(Transform::FlipVertical),
+            [[4, 5, 6, 7], [0, 1, 2, 3]]
+        );
+        assert_eq!(
+            transformed(Transform::Transpose),
+            [[0, 4], [1, 5], [2, 6], [3, 7]]
+        );
+        assert_eq!(
+            transformed(Transform::Transverse),
+            [[7, 3], [6, 2], [5, 1], [4, 0]]
+        );
+    }
+}
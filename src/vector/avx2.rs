@@ -36,6 +36,21 @@ unsafe impl Vector for __m256 {
         _mm256_max_ps(self, other)
     }
 
+    #[inline(always)]
+    unsafe fn vmin(self, other: Self) -> Self {
+        _mm256_min_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn vround(self) -> Self {
+        _mm256_round_ps::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(self)
+    }
+
+    #[inline(always)]
+    unsafe fn vfloor(self) -> Self {
+        _mm256_floor_ps(self)
+    }
+
     #[inline(always)]
     unsafe fn lt(self, other: Self) -> Self::Mask {
         _mm256_cmp_ps::<_CMP_LT_OQ>(self, other)
@@ -85,6 +100,21 @@ unsafe impl Vector for __m256 {
         (_mm256_castsi256_ps(lo), _mm256_castsi256_ps(hi))
     }
 
+    #[inline(always)]
+    unsafe fn gather(base: *const f32, indices: Self) -> Self {
+        _mm256_i32gather_ps::<4>(base, _mm256_cvtps_epi32(indices))
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const f32) -> Self {
+        _mm256_loadu_ps(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, ptr: *mut f32) {
+        _mm256_storeu_ps(ptr, self)
+    }
+
     #[inline(always)]
     unsafe fn load_u8(ptr: *const u8) -> Self {
         let v = ptr.cast::<i64>().read_unaligned();
@@ -285,7 +315,8 @@ unsafe fn f32x8x2_to_u8x16(l: __m256, h: __m256) -> [u8; 16] {
     let l = _mm256_cvtps_epi32(l);
     let h = _mm256_cvtps_epi32(h);
 
-    let v = _mm256_packus_epi32(l, h);
+    // Signed saturation, packus_epi16 would read values above i16::MAX as negative and clip them to 0
+    let v = _mm256_packs_epi32(l, h);
     let v = _mm256_packus_epi16(v, v);
 
     let v = _mm256_permutevar8x32_epi32(v, _mm256_setr_epi32(0, 4, 3, 5, 0, 4, 3, 5));
@@ -307,7 +338,7 @@ unsafe fn f32x8x2_to_u16x16(l: __m256, h: __m256) -> [u16; 16] {
 #[inline(always)]
 unsafe fn f32x8_to_u8x8(v: __m256) -> [u8; 8] {
     let v = _mm256_cvtps_epi32(v);
-    let v = _mm256_packus_epi32(v, v);
+    let v = _mm256_packs_epi32(v, v);
     let v = _mm256_packus_epi16(v, v);
 
     let a = _mm256_extract_epi32::<0>(v);
@@ -331,8 +362,18 @@ unsafe fn f32x8_to_u16x8(v: __m256) -> [u16; 8] {
 
 #[inline(always)]
 unsafe fn interleave_f32x8x4_to_u8x32(r: __m256, g: __m256, b: __m256, a: __m256) -> __m256i {
-    let [rgba_lo, rgba_hi] = interleave_f32x8x4_to_u16x32(r, g, b, a);
+    let r = _mm256_cvtps_epi32(r);
+    let g = _mm256_cvtps_epi32(g);
+    let b = _mm256_cvtps_epi32(b);
+    let a = _mm256_cvtps_epi32(a);
 
+    // Signed saturation, see f32x8x2_to_u8x16
+    let rb = _mm256_packs_epi32(r, b);
+    let ga = _mm256_packs_epi32(g, a);
+
+    let [rgba_lo, rgba_hi] = interleave_epi16x4(rb, ga);
+
+    // Per 128 bit lane this joins pixels 0, 1 with 2, 3 and 4, 5 with 6, 7
     _mm256_packus_epi16(rgba_lo, rgba_hi)
 }
 
@@ -346,6 +387,21 @@ unsafe fn interleave_f32x8x4_to_u16x32(r: __m256, g: __m256, b: __m256, a: __m25
     let rb = _mm256_packus_epi32(r, b);
     let ga = _mm256_packus_epi32(g, a);
 
+    let [rgba_lo, rgba_hi] = interleave_epi16x4(rb, ga);
+
+    // Bring the pixels from 0, 1, 4, 5 & 2, 3, 6, 7 into order
+    [
+        _mm256_permute2x128_si256::<0x20>(rgba_lo, rgba_hi),
+        _mm256_permute2x128_si256::<0x31>(rgba_lo, rgba_hi),
+    ]
+}
+
+/// Interleave the packed 16 bit R & B and G & A values into RGBA pixels
+///
+/// The pixels are ordered per 128 bit lane, so the first vector contains the pixels 0, 1, 4, 5 and the second
+/// 2, 3, 6, 7.
+#[inline(always)]
+unsafe fn interleave_epi16x4(rb: __m256i, ga: __m256i) -> [__m256i; 2] {
     let rgba_lo = _mm256_unpacklo_epi16(rb, ga);
     let rgba_hi = _mm256_unpackhi_epi16(rb, ga);
 
@@ -385,25 +441,13 @@ unsafe fn interleave_f32x8x3_to_u8x24(r: __m256, g: __m256, b: __m256) -> [u8; 2
 
 #[inline(always)]
 unsafe fn interleave_f32x8x3_to_u16x24(r: __m256, g: __m256, b: __m256) -> [u16; 24] {
-    let [rgb_lo, rgb_hi] = interleave_f32x8x4_to_u16x32(r, g, b, _mm256_setzero_ps());
+    let rgba = interleave_f32x8x4_to_u16x32(r, g, b, _mm256_setzero_ps());
+    let rgba: [[u16; 4]; 8] = transmute(rgba);
 
-    #[rustfmt::skip]
-    let idx = _mm256_setr_epi8(
-        0, 1, 2, 3, 4, 5,
-        8, 9, 10, 11, 12, 13,
-        16, 17, 18, 19, 20, 21,
-        24, 25, 26, 27, 28, 29,
-        -128,-128,-128,-128,-128,-128,-128,-128,
-    );
-
-    let rgb_lo = _mm256_shuffle_epi8(rgb_lo, idx);
-    let rgb_hi = _mm256_shuffle_epi8(rgb_hi, idx);
-
-    // This gets optimized to use avx2 by the compiler
-    let [a0, b0, c0, a1, b1, c1, _, _]: [i32; 8] = transmute(rgb_lo);
-    let [a2, b2, c2, a3, b3, c3, _, _]: [i32; 8] = transmute(rgb_hi);
-
-    transmute([a0, b0, c0, a1, b1, c1, a2, b2, c2, a3, b3, c3])
+    rgba.map(|[r, g, b, _]| [r, g, b])
+        .as_flattened()
+        .try_into()
+        .unwrap()
 }
 
 mod math {
@@ -549,6 +593,13 @@ mod math {
 mod tests {
     use super::*;
 
+    #[test]
+    fn saturating_writes() {
+        assert!(is_x86_feature_detected!("avx2"));
+
+        unsafe { crate::vector::check_saturating_writes::<__m256>() }
+    }
+
     #[test]
     fn zip() {
         assert!(is_x86_feature_detected!("avx2"));
@@ -36,6 +36,21 @@ unsafe impl Vector for __m512 {
         _mm512_max_ps(self, other)
     }
 
+    #[inline(always)]
+    unsafe fn vmin(self, other: Self) -> Self {
+        _mm512_min_ps(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn vround(self) -> Self {
+        _mm512_roundscale_ps::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(self)
+    }
+
+    #[inline(always)]
+    unsafe fn vfloor(self) -> Self {
+        _mm512_roundscale_ps::<{ _MM_FROUND_TO_NEG_INF | _MM_FROUND_NO_EXC }>(self)
+    }
+
     #[inline(always)]
     unsafe fn lt(self, other: Self) -> Self::Mask {
         _mm512_cmp_ps_mask::<_CMP_LT_OQ>(self, other)
@@ -85,6 +100,21 @@ unsafe impl Vector for __m512 {
         (a, b)
     }
 
+    #[inline(always)]
+    unsafe fn gather(base: *const f32, indices: Self) -> Self {
+        _mm512_i32gather_ps::<4>(_mm512_cvtps_epi32(indices), base.cast())
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const f32) -> Self {
+        _mm512_loadu_ps(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, ptr: *mut f32) {
+        _mm512_storeu_ps(ptr, self)
+    }
+
     #[inline(always)]
     unsafe fn load_u8(ptr: *const u8) -> Self {
         let v = ptr.cast::<__m128i>().read_unaligned();
@@ -296,34 +326,41 @@ unsafe fn deinterleave_4x(
 
 #[inline(always)]
 pub(crate) unsafe fn f32x16x2_to_u8x32(l: __m512, h: __m512) -> [u8; 32] {
-    let l = _mm512_cvtps_epi32(l);
-    let h = _mm512_cvtps_epi32(h);
+    let l = cvtps_epu32(l);
+    let h = cvtps_epu32(h);
 
-    let l = _mm512_cvtepi32_epi8(l);
-    let h = _mm512_cvtepi32_epi8(h);
+    let l = _mm512_cvtusepi32_epi8(l);
+    let h = _mm512_cvtusepi32_epi8(h);
 
     transmute([l, h])
 }
 
 #[inline(always)]
 pub(crate) unsafe fn f32x16x2_to_u16x32(l: __m512, h: __m512) -> [u16; 32] {
-    let l = _mm512_cvtps_epi32(l);
-    let h = _mm512_cvtps_epi32(h);
+    let l = cvtps_epu32(l);
+    let h = cvtps_epu32(h);
 
-    let l = _mm512_cvtepi32_epi16(l);
-    let h = _mm512_cvtepi32_epi16(h);
+    let l = _mm512_cvtusepi32_epi16(l);
+    let h = _mm512_cvtusepi32_epi16(h);
 
     transmute([l, h])
 }
 
 #[inline(always)]
 pub(crate) unsafe fn f32x16_to_u8x16(v: __m512) -> [u8; 16] {
-    transmute(_mm512_cvtepi32_epi8(_mm512_cvtps_epi32(v)))
+    transmute(_mm512_cvtusepi32_epi8(cvtps_epu32(v)))
 }
 
 #[inline(always)]
 pub(crate) unsafe fn f32x16_to_u16x16(v: __m512) -> [u16; 16] {
-    transmute(_mm512_cvtepi32_epi16(_mm512_cvtps_epi32(v)))
+    transmute(_mm512_cvtusepi32_epi16(cvtps_epu32(v)))
+}
+
+/// Convert to i32 and clamp negative values to zero, so the following unsigned saturating conversions
+/// clip values outside of the target range instead of wrapping them
+#[inline(always)]
+unsafe fn cvtps_epu32(v: __m512) -> __m512i {
+    _mm512_max_epi32(_mm512_cvtps_epi32(v), _mm512_setzero_si512())
 }
 
 #[inline(always)]
@@ -333,8 +370,18 @@ pub(crate) unsafe fn interleave_f32x16x4_to_u8x64(
     b: __m512,
     a: __m512,
 ) -> __m512i {
-    let [rgba_lo, rgba_hi] = interleave_f32x16x4_to_u16x64(r, g, b, a);
+    let r = _mm512_cvtps_epi32(r);
+    let g = _mm512_cvtps_epi32(g);
+    let b = _mm512_cvtps_epi32(b);
+    let a = _mm512_cvtps_epi32(a);
+
+    // Signed saturation, packus_epi16 would read values above i16::MAX as negative and clip them to 0
+    let rb = _mm512_packs_epi32(r, b);
+    let ga = _mm512_packs_epi32(g, a);
 
+    let [rgba_lo, rgba_hi] = interleave_epi16x4(rb, ga);
+
+    // Per 128 bit lane this joins pixels 4n, 4n+1 with 4n+2, 4n+3
     _mm512_packus_epi16(rgba_lo, rgba_hi)
 }
 
@@ -353,6 +400,29 @@ pub(crate) unsafe fn interleave_f32x16x4_to_u16x64(
     let rb = _mm512_packus_epi32(r, b);
     let ga = _mm512_packus_epi32(g, a);
 
+    let [rgba_lo, rgba_hi] = interleave_epi16x4(rb, ga);
+
+    // Bring the pixels from 0, 1, 4, 5, 8, 9, .. & 2, 3, 6, 7, 10, 11, .. into order
+    [
+        _mm512_permutex2var_epi64(
+            rgba_lo,
+            _mm512_setr_epi64(0, 1, 8, 9, 2, 3, 10, 11),
+            rgba_hi,
+        ),
+        _mm512_permutex2var_epi64(
+            rgba_lo,
+            _mm512_setr_epi64(4, 5, 12, 13, 6, 7, 14, 15),
+            rgba_hi,
+        ),
+    ]
+}
+
+/// Interleave the packed 16 bit R & B and G & A values into RGBA pixels
+///
+/// The pixels are ordered per 128 bit lane, so the first vector contains the pixels 0, 1, 4, 5, 8, 9, 12, 13 and the
+/// second 2, 3, 6, 7, 10, 11, 14, 15.
+#[inline(always)]
+unsafe fn interleave_epi16x4(rb: __m512i, ga: __m512i) -> [__m512i; 2] {
     let rgba_lo = _mm512_unpacklo_epi16(rb, ga);
     let rgba_hi = _mm512_unpackhi_epi16(rb, ga);
 
@@ -401,28 +471,13 @@ pub(crate) unsafe fn interleave_f32x16x3_to_u8x48(r: __m512, g: __m512, b: __m51
 
 #[inline(always)]
 pub(crate) unsafe fn interleave_f32x16x3_to_u16x48(r: __m512, g: __m512, b: __m512) -> [u16; 48] {
-    let [rgb_lo, rgb_hi] = interleave_f32x16x4_to_u16x64(r, g, b, _mm512_setzero_ps());
-
-    #[rustfmt::skip]
-    let idx = _mm512_set_epi8(
-        -128, -128, -128, -128, -128, -128, -128, -128,
-        29, 28, 27, 26, 25, 24, 21, 20, 19, 18, 17,
-        16, 13, 12, 11, 10, 9, 8, 5, 4, 3, 2, 1, 0,
-        -128, -128, -128, -128, -128, -128, -128, -128,
-        29, 28, 27, 26, 25, 24, 21, 20, 19, 18, 17,
-        16, 13, 12, 11, 10, 9, 8, 5, 4, 3, 2, 1, 0,
-    );
-
-    let rgb_lo = _mm512_shuffle_epi8(rgb_lo, idx);
-    let rgb_hi = _mm512_shuffle_epi8(rgb_hi, idx);
-
-    let [a0, b0, c0, a1, b1, c1, _, _, a2, b2, c2, a3, b3, c3, _, _]: [i32; 16] = transmute(rgb_lo);
-    let [a4, b4, c4, a5, b5, c5, _, _, a6, b6, c6, a7, b7, c7, _, _]: [i32; 16] = transmute(rgb_hi);
+    let rgba = interleave_f32x16x4_to_u16x64(r, g, b, _mm512_setzero_ps());
+    let rgba: [[u16; 4]; 16] = transmute(rgba);
 
-    transmute([
-        a0, b0, c0, a1, b1, c1, a2, b2, c2, a3, b3, c3, a4, b4, c4, a5, b5, c5, a6, b6, c6, a7, b7,
-        c7,
-    ])
+    rgba.map(|[r, g, b, _]| [r, g, b])
+        .as_flattened()
+        .try_into()
+        .unwrap()
 }
 
 mod math {
@@ -568,6 +623,13 @@ mod math {
 mod tests {
     use super::*;
 
+    #[test]
+    fn saturating_writes() {
+        assert!(is_x86_feature_detected!("avx512f"));
+
+        unsafe { crate::vector::check_saturating_writes::<__m512>() }
+    }
+
     #[test]
     fn zip() {
         assert!(is_x86_feature_detected!("avx512f"));
@@ -10,6 +10,8 @@ pub(crate) mod avx512;
 #[cfg(target_arch = "aarch64")]
 pub(crate) mod neon;
 
+static LANE_INDICES: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
 /// Abstraction over float SIMD vector and common operations
 ///
 /// # Safety
@@ -22,7 +24,7 @@ pub(crate) mod neon;
 pub(crate) unsafe trait Vector: Debug + Copy + 'static {
     /// How many floats (f32) can this vector hold
     const LEN: usize;
-    type Mask;
+    type Mask: Copy;
 
     /// Set all elements in the vector to the given value
     unsafe fn splat(v: f32) -> Self;
@@ -52,6 +54,17 @@ pub(crate) unsafe trait Vector: Debug + Copy + 'static {
         self.vmax(Self::splat(other))
     }
 
+    unsafe fn vmin(self, other: Self) -> Self;
+    unsafe fn vminf(self, other: f32) -> Self {
+        self.vmin(Self::splat(other))
+    }
+
+    /// Round all elements to the nearest integer, ties to even
+    unsafe fn vround(self) -> Self;
+
+    /// Round all elements down to the next integer
+    unsafe fn vfloor(self) -> Self;
+
     /// Compare all element in self to other and return a mask with the results a < b
     unsafe fn lt(self, other: Self) -> Self::Mask;
     unsafe fn ltf(self, other: f32) -> Self::Mask {
@@ -86,6 +99,32 @@ pub(crate) unsafe trait Vector: Debug + Copy + 'static {
     /// Return [a0, a2, b0, b2] and [a1, a3, b1, b3]
     unsafe fn unzip(self, other: Self) -> (Self, Self);
 
+    /// Load LEN floats from `base` at the given indices, which must be positive integers stored as floats
+    ///
+    /// # Safety
+    ///
+    /// `base` must be valid to read at every index
+    unsafe fn gather(base: *const f32, indices: Self) -> Self;
+
+    /// Load LEN floats from `ptr`
+    ///
+    /// # Safety
+    ///
+    /// Pointer must be valid to read Self::LEN floats
+    unsafe fn load(ptr: *const f32) -> Self;
+
+    /// Store all LEN floats to `ptr`
+    ///
+    /// # Safety
+    ///
+    /// Pointer must be valid to write Self::LEN floats
+    unsafe fn store(self, ptr: *mut f32);
+
+    /// Returns a vector containing the index of every lane, `[0.0, 1.0, 2.0, ...]`
+    unsafe fn lane_indices() -> Self {
+        Self::load_u8(LANE_INDICES.as_ptr())
+    }
+
     /// Load LEN packed bytes and unpack them to f32 by scattering them to [b0, 0, 0, 0, b1, 0, 0, 0, b2, 0, 0, 0, ...]
     /// and then converting to floats
     ///
@@ -158,6 +197,21 @@ unsafe impl Vector for f32 {
         self.max(other)
     }
 
+    #[inline(always)]
+    unsafe fn vmin(self, other: Self) -> Self {
+        self.min(other)
+    }
+
+    #[inline(always)]
+    unsafe fn vround(self) -> Self {
+        self.round_ties_even()
+    }
+
+    #[inline(always)]
+    unsafe fn vfloor(self) -> Self {
+        self.floor()
+    }
+
     #[inline(always)]
     unsafe fn lt(self, other: Self) -> Self::Mask {
         self < other
@@ -197,6 +251,21 @@ unsafe impl Vector for f32 {
         (self, other)
     }
 
+    #[inline(always)]
+    unsafe fn gather(base: *const f32, indices: Self) -> Self {
+        base.add(indices as usize).read()
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const f32) -> Self {
+        ptr.read_unaligned()
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, ptr: *mut f32) {
+        ptr.write_unaligned(self)
+    }
+
     #[inline(always)]
     unsafe fn load_u8(ptr: *const u8) -> Self {
         Self::from(ptr.read_unaligned())
@@ -232,44 +301,46 @@ unsafe impl Vector for f32 {
 
     #[inline(always)]
     unsafe fn write_u8(self, ptr: *mut u8) {
-        ptr.write(self as u8)
+        ptr.write(self.round_ties_even() as u8)
     }
     #[inline(always)]
     unsafe fn write_u8_2x(v0: Self, v1: Self, ptr: *mut u8) {
-        ptr.cast::<[u8; 2]>().write_unaligned([v0 as u8, v1 as u8]);
+        ptr.cast::<[u8; 2]>()
+            .write_unaligned([v0.round_ties_even() as u8, v1.round_ties_even() as u8]);
     }
     #[inline(always)]
     unsafe fn write_u16(self, ptr: *mut u8) {
-        ptr.cast::<u16>().write_unaligned(self as u16);
+        ptr.cast::<u16>()
+            .write_unaligned(self.round_ties_even() as u16);
     }
     #[inline(always)]
     unsafe fn write_u16_2x(v0: Self, v1: Self, ptr: *mut u8) {
         ptr.cast::<[u16; 2]>()
-            .write_unaligned([v0 as u16, v1 as u16]);
+            .write_unaligned([v0.round_ties_even() as u16, v1.round_ties_even() as u16]);
     }
 
     #[inline(always)]
     unsafe fn write_interleaved_3x_2x_u8(this: [[Self; 3]; 2], ptr: *mut u8) {
         ptr.cast::<[[u8; 3]; 2]>()
-            .write_unaligned(this.map(|f| f.map(|f| f as u8)));
+            .write_unaligned(this.map(|f| f.map(|f| f.round_ties_even() as u8)));
     }
 
     #[inline(always)]
     unsafe fn write_interleaved_3x_2x_u16(this: [[Self; 3]; 2], ptr: *mut u8) {
         ptr.cast::<[[u16; 3]; 2]>()
-            .write_unaligned(this.map(|f| f.map(|f| f as u16)));
+            .write_unaligned(this.map(|f| f.map(|f| f.round_ties_even() as u16)));
     }
 
     #[inline(always)]
     unsafe fn write_interleaved_4x_2x_u8(this: [[Self; 4]; 2], ptr: *mut u8) {
         ptr.cast::<[[u8; 4]; 2]>()
-            .write_unaligned(this.map(|f| f.map(|f| f as u8)));
+            .write_unaligned(this.map(|f| f.map(|f| f.round_ties_even() as u8)));
     }
 
     #[inline(always)]
     unsafe fn write_interleaved_4x_2x_u16(this: [[Self; 4]; 2], ptr: *mut u8) {
         ptr.cast::<[[u16; 4]; 2]>()
-            .write_unaligned(this.map(|f| f.map(|f| f as u16)));
+            .write_unaligned(this.map(|f| f.map(|f| f.round_ties_even() as u16)));
     }
 
     #[inline(always)]
@@ -281,3 +352,81 @@ unsafe impl Vector for f32 {
         DynRgbaReaderSpec::<f32>::dyn_read(v, x, y)
     }
 }
+
+/// Write samples outside of the u8 & u16 range with every write function of `V` and check that they are clamped
+#[cfg(test)]
+pub(crate) unsafe fn check_saturating_writes<V: Vector>() {
+    #[rustfmt::skip]
+    const VALUES: [f32; 16] = [
+        -1e9, -70000.0, -300.0, -1.0, -0.4, 0.0, 0.6, 100.0,
+        254.6, 255.4, 256.0, 300.0, 65535.4, 65536.0, 70000.0, 1e9,
+    ];
+
+    fn check<T: PartialEq + Debug>(out: &[T], expected: impl Fn(usize) -> T, write: &str) {
+        for (i, value) in out.iter().enumerate() {
+            assert_eq!(*value, expected(i), "{write} at {i}");
+        }
+    }
+
+    for start in 0..VALUES.len() {
+        // Rotate the values, so every value is placed in every lane once
+        let values: [f32; 16] = std::array::from_fn(|i| VALUES[(start + i) % VALUES.len()]);
+        let value = |pixel: usize| VALUES[(start + pixel % V::LEN) % VALUES.len()].round();
+        let u8_at = |pixel: usize| value(pixel).clamp(0.0, 255.0) as u8;
+        let u16_at = |pixel: usize| value(pixel).clamp(0.0, 65535.0) as u16;
+
+        let v = V::gather(values.as_ptr(), V::lane_indices());
+
+        let mut out = [0u8; 16 * 4 * 2];
+
+        v.write_u8(out.as_mut_ptr());
+        check(&out[..V::LEN], u8_at, "write_u8");
+
+        V::write_u8_2x(v, v, out.as_mut_ptr());
+        check(&out[..V::LEN * 2], u8_at, "write_u8_2x");
+
+        V::write_interleaved_3x_2x_u8([[v; 3]; 2], out.as_mut_ptr());
+        check(
+            &out[..V::LEN * 6],
+            |i| u8_at(i / 3),
+            "write_interleaved_3x_2x_u8",
+        );
+
+        V::write_interleaved_4x_2x_u8([[v; 4]; 2], out.as_mut_ptr());
+        check(
+            &out[..V::LEN * 8],
+            |i| u8_at(i / 4),
+            "write_interleaved_4x_2x_u8",
+        );
+
+        let mut out = [0u16; 16 * 4 * 2];
+
+        v.write_u16(out.as_mut_ptr().cast());
+        check(&out[..V::LEN], u16_at, "write_u16");
+
+        V::write_u16_2x(v, v, out.as_mut_ptr().cast());
+        check(&out[..V::LEN * 2], u16_at, "write_u16_2x");
+
+        V::write_interleaved_3x_2x_u16([[v; 3]; 2], out.as_mut_ptr().cast());
+        check(
+            &out[..V::LEN * 6],
+            |i| u16_at(i / 3),
+            "write_interleaved_3x_2x_u16",
+        );
+
+        V::write_interleaved_4x_2x_u16([[v; 4]; 2], out.as_mut_ptr().cast());
+        check(
+            &out[..V::LEN * 8],
+            |i| u16_at(i / 4),
+            "write_interleaved_4x_2x_u16",
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn saturating_writes() {
+        unsafe { super::check_saturating_writes::<f32>() }
+    }
+}
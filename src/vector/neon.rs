@@ -33,6 +33,21 @@ unsafe impl Vector for float32x4_t {
         vmaxq_f32(self, other)
     }
 
+    #[inline(always)]
+    unsafe fn vmin(self, other: Self) -> Self {
+        vminq_f32(self, other)
+    }
+
+    #[inline(always)]
+    unsafe fn vround(self) -> Self {
+        vrndnq_f32(self)
+    }
+
+    #[inline(always)]
+    unsafe fn vfloor(self) -> Self {
+        vrndmq_f32(self)
+    }
+
     #[inline(always)]
     unsafe fn lt(self, other: Self) -> Self::Mask {
         vcaltq_f32(self, other)
@@ -76,6 +91,31 @@ unsafe impl Vector for float32x4_t {
         (a, b)
     }
 
+    #[inline(always)]
+    unsafe fn gather(base: *const f32, indices: Self) -> Self {
+        // No gather instruction available, load every lane individually
+        let indices = vcvtq_u32_f32(indices);
+
+        let v = [
+            base.add(vgetq_lane_u32::<0>(indices) as usize).read(),
+            base.add(vgetq_lane_u32::<1>(indices) as usize).read(),
+            base.add(vgetq_lane_u32::<2>(indices) as usize).read(),
+            base.add(vgetq_lane_u32::<3>(indices) as usize).read(),
+        ];
+
+        vld1q_f32(v.as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const f32) -> Self {
+        vld1q_f32(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn store(self, ptr: *mut f32) {
+        vst1q_f32(ptr, self)
+    }
+
     #[inline(always)]
     unsafe fn load_u8(ptr: *const u8) -> Self {
         let v = ptr.cast::<[u8; 4]>().read_unaligned();
@@ -260,50 +300,50 @@ unsafe impl Vector for float32x4_t {
 
 #[inline(always)]
 unsafe fn float32x4x2_to_u8x8(l: float32x4_t, h: float32x4_t) -> [u8; 8] {
-    let l = vcvtq_u32_f32(l);
+    let l = vcvtnq_u32_f32(l);
     let l = vminq_u32(l, vdupq_n_u32(u8::MAX as u32));
-    let l = vmovn_u32(l);
+    let l = vqmovn_u32(l);
 
-    let h = vcvtq_u32_f32(h);
+    let h = vcvtnq_u32_f32(h);
     let h = vminq_u32(h, vdupq_n_u32(u8::MAX as u32));
-    let h = vmovn_u32(h);
+    let h = vqmovn_u32(h);
 
     let v = transmute::<[uint16x4_t; 2], uint16x8_t>([l, h]);
 
-    transmute(vmovn_u16(v))
+    transmute(vqmovn_u16(v))
 }
 
 #[inline(always)]
 unsafe fn float32x4x2_to_u16x8(l: float32x4_t, h: float32x4_t) -> [u16; 8] {
-    let l = vcvtq_u32_f32(l);
+    let l = vcvtnq_u32_f32(l);
     let l = vminq_u32(l, vdupq_n_u32(u16::MAX as u32));
-    let l = vmovn_u32(l);
+    let l = vqmovn_u32(l);
 
-    let h = vcvtq_u32_f32(h);
+    let h = vcvtnq_u32_f32(h);
     let h = vminq_u32(h, vdupq_n_u32(u16::MAX as u32));
-    let h = vmovn_u32(h);
+    let h = vqmovn_u32(h);
 
     transmute([l, h])
 }
 
 #[inline(always)]
 unsafe fn float32x4_to_u8x4(i: float32x4_t) -> [u8; 4] {
-    let i = vcvtq_u32_f32(i);
+    let i = vcvtnq_u32_f32(i);
     let i = vminq_u32(i, vdupq_n_u32(255));
-    let i = vmovn_u32(i);
+    let i = vqmovn_u32(i);
 
     let v = transmute::<[uint16x4_t; 2], uint16x8_t>([i, i]);
 
-    let [a, b, c, d, ..] = transmute::<uint8x8_t, [u8; 8]>(vmovn_u16(v));
+    let [a, b, c, d, ..] = transmute::<uint8x8_t, [u8; 8]>(vqmovn_u16(v));
 
     [a, b, c, d]
 }
 
 #[inline(always)]
 unsafe fn float32x4_to_u16x4(i: float32x4_t) -> [u16; 4] {
-    let i = vcvtq_u32_f32(i);
+    let i = vcvtnq_u32_f32(i);
     let i = vminq_u32(i, vdupq_n_u32(u16::MAX as u32));
-    let i = vmovn_u32(i);
+    let i = vqmovn_u32(i);
 
     transmute(i)
 }
@@ -480,6 +520,13 @@ mod math {
 mod tests {
     use super::*;
 
+    #[test]
+    fn saturating_writes() {
+        assert!(is_aarch64_feature_detected!("neon"));
+
+        unsafe { crate::vector::check_saturating_writes::<float32x4_t>() }
+    }
+
     #[test]
     fn zip() {
         assert!(is_aarch64_feature_detected!("neon"));
@@ -1,9 +1,17 @@
 use ezk_image::{
-    ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer, Image, ImageRef, PixelFormat,
-    RgbColorInfo, YuvColorInfo, convert_multi_thread, resize::Resizer,
+    AlphaBackground, ColorAdjustments, ColorInfo, ColorPrimaries, ColorSpace, ColorTransfer,
+    ConvertOptions, Image, ImageMut, ImageRef, Lut3D, LutInterpolation, PixelFormat, RgbColorInfo,
+    TestPattern, Transform, Window, YuvColorInfo, clear, convert, convert_color,
+    convert_multi_thread, convert_multi_thread_with_options, convert_with_options, fill,
+    fill_test_pattern, light_level, metrics, overlay,
+    resize::{ChromaSiting, FitMode, Padding, ResizeError, Resizer, SourceRect},
+    scopes,
+    statistics::{self, Channel},
+    transform,
 };
 use fir::ResizeAlg;
 use image::Rgb;
+use std::sync::Arc;
 
 fn make_rgba8_image(
     width: usize,
@@ -43,6 +51,8 @@ fn make_rgba8_image(
         ColorInfo::RGB(RgbColorInfo {
             transfer,
             primaries,
+            full_range: true,
+            premultiplied_alpha: false,
         }),
     )
     .unwrap()
@@ -79,7 +89,1604 @@ fn all_from_rgb_and_back() {
             rgb.planes().next().unwrap().0.to_vec(),
         )
         .unwrap()
-        .save(format!("tests/ALL_{format:?}.png"))
+        .save(format!(
+            "{}/ALL_{format:?}.png",
+            env!("CARGO_TARGET_TMPDIR")
+        ))
         .unwrap();
     }
 }
+
+#[test]
+fn ycocg_r_lossless() {
+    let (width, height) = (128, 64);
+
+    let rgb_color = ColorInfo::RGB(RgbColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        full_range: true,
+        premultiplied_alpha: false,
+    });
+    let yuv_color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::YCoCgR,
+        full_range: true,
+    });
+
+    // Cheap deterministic noise covering the full u8 range
+    let mut state = 0x2545_f491_u32;
+    let data = (0..PixelFormat::RGB.buffer_size(width, height))
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect::<Vec<u8>>();
+
+    let src = Image::from_buffer(PixelFormat::RGB, data, None, width, height, rgb_color).unwrap();
+
+    let mut yuv = Image::blank(PixelFormat::I444, width, height, yuv_color);
+    assert!(convert_multi_thread(&src, &mut yuv).is_err());
+
+    for format in [PixelFormat::I410, PixelFormat::I412] {
+        let mut yuv = Image::blank(format, width, height, yuv_color);
+        convert_multi_thread(&src, &mut yuv).unwrap();
+
+        let mut dst = Image::blank(PixelFormat::RGB, width, height, rgb_color);
+        convert_multi_thread(&yuv, &mut dst).unwrap();
+
+        assert!(src.planes().next().unwrap().0 == dst.planes().next().unwrap().0);
+    }
+}
+
+#[test]
+fn ycocg_r_lossless_n_bit() {
+    for bits in [10, 12] {
+        let max_value = ((1u32 << bits) - 1) as f32;
+        let max_chroma = ((1u32 << (bits + 1)) - 1) as f32;
+
+        let rgb_color = ColorInfo::RGB(RgbColorInfo {
+            transfer: ColorTransfer::SRGB,
+            primaries: ColorPrimaries::BT709,
+            full_range: true,
+            premultiplied_alpha: false,
+        });
+        let yuv_color = ColorInfo::YUV(YuvColorInfo {
+            transfer: ColorTransfer::SRGB,
+            primaries: ColorPrimaries::BT709,
+            space: ColorSpace::YCoCgR,
+            full_range: true,
+        });
+
+        // Corners of the RGB cube plus deterministic noise over all N-bit values
+        let mut state = 0x2545_f491_u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % (1 << bits)) as f32
+        };
+
+        let corners = (0..8).map(|i| {
+            [i & 1, (i >> 1) & 1, (i >> 2) & 1].map(|c| if c == 1 { max_value } else { 0.0 })
+        });
+        let noise = (0..20_000)
+            .map(|_| [next(), next(), next()])
+            .collect::<Vec<_>>();
+
+        for rgb in corners.chain(noise) {
+            let [y, cg, co] = convert_color(&rgb_color, bits, &yuv_color, bits, rgb)
+                .unwrap()
+                .map(f32::round);
+
+            assert!(
+                (0.0..=max_value).contains(&y),
+                "{bits}-bit {rgb:?} -> y={y}"
+            );
+            assert!(
+                (0.0..=max_chroma).contains(&cg),
+                "{bits}-bit {rgb:?} -> cg={cg}"
+            );
+            assert!(
+                (0.0..=max_chroma).contains(&co),
+                "{bits}-bit {rgb:?} -> co={co}"
+            );
+
+            let back = convert_color(&yuv_color, bits, &rgb_color, bits, [y, cg, co])
+                .unwrap()
+                .map(f32::round);
+
+            assert_eq!(back, rgb, "{bits}-bit via {:?}", [y, cg, co]);
+        }
+    }
+}
+
+#[test]
+fn limited_range_rgb() {
+    let (width, height) = (16, 2);
+
+    let color = |full_range| {
+        ColorInfo::RGB(RgbColorInfo {
+            transfer: ColorTransfer::SRGB,
+            primaries: ColorPrimaries::BT709,
+            full_range,
+            premultiplied_alpha: false,
+        })
+    };
+
+    let data = [16u8, 235, 126].repeat(width * height);
+    let src =
+        Image::from_buffer(PixelFormat::RGB, data, None, width, height, color(false)).unwrap();
+
+    let mut full = Image::blank(PixelFormat::RGB, width, height, color(true));
+    convert_multi_thread(&src, &mut full).unwrap();
+
+    assert!(
+        full.planes()
+            .next()
+            .unwrap()
+            .0
+            .chunks(3)
+            .all(|px| px == [0, 255, 128])
+    );
+
+    let mut limited = Image::blank(PixelFormat::RGBA, width, height, color(false));
+    convert_multi_thread(&full, &mut limited).unwrap();
+
+    assert!(
+        limited
+            .planes()
+            .next()
+            .unwrap()
+            .0
+            .chunks(4)
+            .all(|px| px == [16, 235, 126, 255])
+    );
+}
+
+#[test]
+fn preserve_out_of_range() {
+    let (width, height) = (32, 2);
+
+    let color = |transfer| {
+        ColorInfo::YUV(YuvColorInfo {
+            transfer,
+            primaries: ColorPrimaries::BT709,
+            space: ColorSpace::BT709,
+            full_range: false,
+        })
+    };
+
+    // Sub-black, black, white and superwhite luma with neutral chroma
+    let luma = [8u8, 16, 235, 250].repeat(width * height / 4);
+    let chroma = vec![128u8; width * height];
+    let data = [luma, chroma.clone(), chroma].concat();
+
+    let src = Image::from_buffer(
+        PixelFormat::I444,
+        data,
+        None,
+        width,
+        height,
+        color(ColorTransfer::SDR),
+    )
+    .unwrap();
+
+    let options = ConvertOptions {
+        preserve_out_of_range: true,
+        ..Default::default()
+    };
+
+    let mut dst = Image::blank(
+        PixelFormat::I444,
+        width,
+        height,
+        color(ColorTransfer::Gamma22),
+    );
+    convert_multi_thread_with_options(&src, &mut dst, &options).unwrap();
+
+    let y = dst.planes().next().unwrap().0;
+
+    for px in y.chunks(4) {
+        assert!(px[0] < 16, "{px:?}");
+        assert_eq!(px[1], 16);
+        assert_eq!(px[2], 235);
+        assert!(px[3] > 235, "{px:?}");
+    }
+}
+
+fn rgb_color(premultiplied_alpha: bool) -> ColorInfo {
+    ColorInfo::RGB(RgbColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        full_range: true,
+        premultiplied_alpha,
+    })
+}
+
+#[test]
+fn premultiplied_alpha_convert() {
+    let (width, height) = (16, 2);
+
+    let data = [64u8, 32, 16, 128, 0, 0, 0, 0].repeat(width * height / 2);
+    let src = Image::from_buffer(
+        PixelFormat::BGRA,
+        data,
+        None,
+        width,
+        height,
+        rgb_color(true),
+    )
+    .unwrap();
+
+    let mut straight = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+    convert_multi_thread(&src, &mut straight).unwrap();
+
+    for px in straight.planes().next().unwrap().0.chunks(8) {
+        assert_eq!(px, [32, 64, 128, 128, 0, 0, 0, 0]);
+    }
+
+    let mut premultiplied = Image::blank(PixelFormat::BGRA, width, height, rgb_color(true));
+    convert_multi_thread(&straight, &mut premultiplied).unwrap();
+
+    assert_eq!(
+        premultiplied.planes().next().unwrap().0,
+        src.planes().next().unwrap().0
+    );
+}
+
+#[test]
+fn premultiplied_alpha_resize() {
+    // Opaque red next to a fully transparent white pixel, the white must not bleed into the red when downscaling
+    for (format, px) in [
+        (PixelFormat::RGBA, [[255u8, 0, 0, 255], [255, 255, 255, 0]]),
+        (PixelFormat::ARGB, [[255u8, 255, 0, 0], [0, 255, 255, 255]]),
+    ] {
+        let src = Image::from_buffer(
+            format,
+            px.concat().repeat(8 * 4),
+            None,
+            16,
+            4,
+            rgb_color(false),
+        )
+        .unwrap();
+        let mut dst = Image::blank(format, 8, 2, rgb_color(false));
+
+        Resizer::new(ResizeAlg::Convolution(fir::FilterType::Bilinear))
+            .resize(&src, &mut dst)
+            .unwrap();
+
+        let alpha_index = if format == PixelFormat::RGBA { 3 } else { 0 };
+
+        for px in dst.planes().next().unwrap().0.chunks(4) {
+            let a = px[alpha_index];
+            let [r, g, b] = match format {
+                PixelFormat::RGBA => [px[0], px[1], px[2]],
+                _ => [px[1], px[2], px[3]],
+            };
+
+            assert!(a > 100 && a < 155, "{format:?} {px:?}");
+            assert!(r > 250 && g < 5 && b < 5, "{format:?} {px:?}");
+        }
+    }
+}
+
+#[test]
+fn alpha_composite_color() {
+    let (width, height) = (70, 4);
+
+    let data = [255u8, 0, 0, 128].repeat(width * height);
+    let src = Image::from_buffer(
+        PixelFormat::RGBA,
+        data,
+        None,
+        width,
+        height,
+        rgb_color(false),
+    )
+    .unwrap();
+
+    for (composite_in_linear_light, expected) in [(false, [128, 0, 127]), (true, [188, 0, 187])] {
+        let options = ConvertOptions {
+            alpha_background: Some(AlphaBackground::Color([0.0, 0.0, 1.0])),
+            composite_in_linear_light,
+            ..Default::default()
+        };
+
+        let mut dst = Image::blank(PixelFormat::RGB, width, height, rgb_color(false));
+        convert_multi_thread_with_options(&src, &mut dst, &options).unwrap();
+
+        for px in dst.planes().next().unwrap().0.chunks(3) {
+            assert_eq!(px, expected);
+        }
+    }
+}
+
+#[test]
+fn alpha_composite_checkerboard() {
+    let (width, height) = (70, 64);
+
+    let src = Image::blank(PixelFormat::BGRA, width, height, rgb_color(false));
+
+    let options = ConvertOptions {
+        alpha_background: Some(AlphaBackground::Checkerboard {
+            size: 3,
+            colors: [[0.0; 3], [1.0; 3]],
+        }),
+        ..Default::default()
+    };
+
+    let mut single = Image::blank(PixelFormat::RGB, width, height, rgb_color(false));
+    convert_with_options(&src, &mut single, &options).unwrap();
+
+    let mut multi = Image::blank(PixelFormat::RGB, width, height, rgb_color(false));
+    convert_multi_thread_with_options(&src, &mut multi, &options).unwrap();
+
+    let (plane, stride) = single.planes().next().unwrap();
+    assert_eq!(plane, multi.planes().next().unwrap().0);
+
+    for y in 0..height {
+        for x in 0..width {
+            let expected = if (x / 3 + y / 3) % 2 == 0 { 0 } else { 255 };
+
+            assert_eq!(plane[y * stride + x * 3..][..3], [expected; 3], "{x} {y}");
+        }
+    }
+}
+
+#[test]
+fn overlay_onto_i420() {
+    let (width, height) = (32, 16);
+
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut data = vec![100u8; width * height];
+    data.resize(PixelFormat::I420.buffer_size(width, height), 128);
+    let mut dst = Image::from_buffer(PixelFormat::I420, data, None, width, height, color).unwrap();
+
+    // Opaque red overlay at odd coordinates, partially outside of the destination
+    let (x, y, overlay_width, overlay_height) = (27, 13, 7, 5);
+
+    let data = [255u8, 0, 0, 255].repeat(overlay_width * overlay_height);
+    let overlay_image = Image::from_buffer(
+        PixelFormat::RGBA,
+        data,
+        None,
+        overlay_width,
+        overlay_height,
+        rgb_color(false),
+    )
+    .unwrap();
+
+    overlay(&overlay_image, &mut dst, x, y).unwrap();
+
+    let mut planes = dst.planes();
+    let (y_plane, y_stride) = planes.next().unwrap();
+    let (u_plane, uv_stride) = planes.next().unwrap();
+    let (v_plane, _) = planes.next().unwrap();
+
+    for py in 0..height {
+        for px in 0..width {
+            let covered = px >= x && py >= y;
+            let expected = if covered { 63 } else { 100 };
+
+            assert_eq!(y_plane[py * y_stride + px], expected, "Y at {px} {py}");
+        }
+    }
+
+    for py in 0..height / 2 {
+        for px in 0..width / 2 {
+            let u = u_plane[py * uv_stride + px];
+            let v = v_plane[py * uv_stride + px];
+
+            if px >= x / 2 && py >= y / 2 {
+                // Blocks on the edge are only partially red
+                assert!(u < 128 && v > 128, "UV at {px} {py}");
+            } else {
+                assert_eq!([u, v], [128, 128], "UV at {px} {py}");
+            }
+        }
+    }
+}
+
+#[test]
+fn overlay_keeps_uncovered_samples() {
+    let (width, height) = (16, 16);
+
+    let yuv_color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    // Half transparent overlay at odd coordinates with a fully transparent left column and top row
+    let (x, y, overlay_width, overlay_height) = (5, 3, 6, 7);
+    let is_visible = |px: usize, py: usize| {
+        px > x && py > y && px < x + overlay_width && py < y + overlay_height
+    };
+
+    let data: Vec<u8> = (0..overlay_height)
+        .flat_map(|row| (0..overlay_width).map(move |column| (row, column)))
+        .flat_map(|(row, column)| {
+            let alpha = if row == 0 || column == 0 { 0 } else { 128 };
+            [0, 0, 255, alpha]
+        })
+        .collect();
+
+    let overlay_image = Image::from_buffer(
+        PixelFormat::RGBA,
+        data,
+        None,
+        overlay_width,
+        overlay_height,
+        rgb_color(false),
+    )
+    .unwrap();
+
+    // The color of fully transparent RGBA pixels is lost when blending, so they must never be rewritten
+    for (format, color) in [
+        (PixelFormat::I420, yuv_color),
+        (PixelFormat::RGBA, rgb_color(false)),
+    ] {
+        let mut state = 0x2545_f491u32;
+        let data: Vec<u8> = (0..format.buffer_size(width, height))
+            .map(|i| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+
+                if format == PixelFormat::RGBA && i % 4 == 3 {
+                    0
+                } else {
+                    state as u8
+                }
+            })
+            .collect();
+
+        let original = Image::from_buffer(format, data, None, width, height, color).unwrap();
+
+        let mut dst = original.clone();
+        overlay(&overlay_image, &mut dst, x, y).unwrap();
+
+        for (i, ((plane, stride), (original_plane, _))) in
+            dst.planes().zip(original.planes()).enumerate()
+        {
+            // Chroma planes of I420 are subsampled in both directions
+            let subsampling = if i == 0 { 1 } else { 2 };
+            let bytes_per_pixel = stride * subsampling / width;
+
+            for py in 0..height / subsampling {
+                for px in 0..width / subsampling {
+                    let covered = (0..subsampling * subsampling).any(|corner| {
+                        is_visible(
+                            px * subsampling + corner % subsampling,
+                            py * subsampling + corner / subsampling,
+                        )
+                    });
+
+                    let index = py * stride + px * bytes_per_pixel;
+                    let samples = index..index + bytes_per_pixel;
+
+                    if covered {
+                        assert_ne!(plane[samples.clone()], original_plane[samples]);
+                    } else {
+                        assert_eq!(
+                            plane[samples.clone()],
+                            original_plane[samples],
+                            "{format:?} plane {i} at {px} {py}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn lut_matches_scalar() {
+    let (width, height) = (70, 8);
+
+    // Non-linear LUT with crosstalk between the channels
+    let size = 9;
+    let max = (size - 1) as f32;
+
+    let mut entries = vec![];
+    for b in 0..size {
+        for g in 0..size {
+            for r in 0..size {
+                let [r, g, b] = [r as f32 / max, g as f32 / max, b as f32 / max];
+                entries.push([r * r, (g + b) / 2.0, 1.0 - b]);
+            }
+        }
+    }
+
+    let lut = Arc::new(Lut3D::new(size, entries).unwrap());
+
+    let data: Vec<u8> = (0..width * height * 4)
+        .map(|i| (i * 37 % 256) as u8)
+        .collect();
+    let src = Image::from_buffer(
+        PixelFormat::RGBA,
+        data,
+        None,
+        width,
+        height,
+        rgb_color(false),
+    )
+    .unwrap();
+
+    for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+        let options = ConvertOptions {
+            lut: Some(lut.clone()),
+            lut_interpolation: interpolation,
+            ..Default::default()
+        };
+
+        let mut dst = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+        convert_multi_thread_with_options(&src, &mut dst, &options).unwrap();
+
+        let src_pixels = src.planes().next().unwrap().0.chunks(4);
+        let dst_pixels = dst.planes().next().unwrap().0.chunks(4);
+
+        for (src_px, dst_px) in src_pixels.zip(dst_pixels) {
+            let rgb = [0, 1, 2].map(|i| f32::from(src_px[i]) / 255.0);
+            let expected = lut
+                .apply(interpolation, rgb)
+                .map(|v| (v * 255.0).round() as i32);
+
+            for i in 0..3 {
+                assert!(
+                    (i32::from(dst_px[i]) - expected[i]).abs() <= 1,
+                    "{interpolation:?} {src_px:?} {dst_px:?} {expected:?}"
+                );
+            }
+
+            assert_eq!(src_px[3], dst_px[3]);
+        }
+    }
+}
+
+#[test]
+fn color_adjustments() {
+    let (width, height) = (70, 4);
+
+    let convert_pixel = |px: [u8; 4], adjustments: ColorAdjustments| {
+        let src = Image::from_buffer(
+            PixelFormat::RGBA,
+            px.repeat(width * height),
+            None,
+            width,
+            height,
+            rgb_color(false),
+        )
+        .unwrap();
+
+        let options = ConvertOptions {
+            color_adjustments: Some(adjustments),
+            ..Default::default()
+        };
+
+        let mut dst = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+        convert_multi_thread_with_options(&src, &mut dst, &options).unwrap();
+
+        let plane = dst.planes().next().unwrap().0;
+        assert!(plane.chunks(4).all(|p| p == &plane[..4]));
+
+        <[u8; 4]>::try_from(&plane[..4]).unwrap()
+    };
+
+    let px = [200, 100, 50, 255];
+
+    assert_eq!(convert_pixel(px, ColorAdjustments::default()), px);
+
+    let hue = ColorAdjustments {
+        hue: 360.0,
+        ..Default::default()
+    };
+    assert_eq!(convert_pixel(px, hue), px);
+
+    let [r, g, b, a] = convert_pixel(
+        px,
+        ColorAdjustments {
+            saturation: 0.0,
+            ..Default::default()
+        },
+    );
+    assert!(r == g && g == b && a == 255);
+
+    let brightness = ColorAdjustments {
+        brightness: 0.2,
+        ..Default::default()
+    };
+    assert_eq!(convert_pixel(px, brightness), [251, 151, 101, 255]);
+
+    let contrast = ColorAdjustments {
+        contrast: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(convert_pixel(px, contrast), [255, 72, 0, 255]);
+
+    let gamma = ColorAdjustments {
+        gamma: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        convert_pixel([64, 128, 255, 255], gamma),
+        [128, 181, 255, 255]
+    );
+
+    let white_balance = ColorAdjustments {
+        white_balance: [1.0, 2.0, 0.5],
+        ..Default::default()
+    };
+    assert_eq!(convert_pixel(px, white_balance), [200, 200, 25, 255]);
+}
+
+#[test]
+fn test_patterns() {
+    let (width, height) = (112, 12);
+
+    let mut rgb = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+    fill_test_pattern(&mut rgb, TestPattern::EbuBars).unwrap();
+
+    let (plane, stride) = rgb.planes().next().unwrap();
+    let bar = |i: usize| &plane[(height - 1) * stride + (i * width / 8 + 1) * 4..][..4];
+
+    assert_eq!(bar(0), [255, 255, 255, 255]);
+    assert_eq!(bar(1), [191, 191, 0, 255]);
+    assert_eq!(bar(6), [0, 0, 191, 255]);
+    assert_eq!(bar(7), [0, 0, 0, 255]);
+
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut yuv = Image::blank(PixelFormat::I420, width, height, color);
+    fill_test_pattern(&mut yuv, TestPattern::SmpteBars).unwrap();
+
+    let (y_plane, y_stride) = yuv.planes().next().unwrap();
+    let luma = |x: usize, y: usize| y_plane[y * y_stride + x];
+
+    // 40% gray side patch and 75% white bar
+    assert_eq!(luma(0, 0), 104);
+    assert_eq!(luma(20, 0), 180);
+
+    // Black, -2% and +4% PLUGE steps in the bottom row
+    assert_eq!(luma(20, height - 1), 16);
+    assert_eq!(luma(68, height - 1), 12);
+    assert_eq!(luma(83, height - 1), 25);
+
+    assert!(
+        fill_test_pattern(
+            &mut Image::blank(PixelFormat::RGBA, 3, 2, rgb_color(false)),
+            TestPattern::Ramp
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn metrics_after_conversion() {
+    let (width, height) = (192, 192);
+
+    let reference = make_rgba8_image(width, height, ColorPrimaries::BT709, ColorTransfer::SRGB);
+
+    let yuv_color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut yuv = Image::blank(PixelFormat::I420, width, height, yuv_color);
+    convert_multi_thread(&reference, &mut yuv).unwrap();
+
+    let same = metrics::compare(&reference, &reference).unwrap();
+    assert!(same.planes.iter().all(|p| p.psnr.is_infinite()));
+    assert_eq!(same.ciede2000.unwrap().max, 0.0);
+
+    let metrics = metrics::compare(&reference, &yuv).unwrap();
+
+    assert_eq!(metrics.planes.len(), 4);
+    assert!(metrics.delta_e_itp.is_none());
+
+    for plane in &metrics.planes[..3] {
+        assert!(plane.psnr > 25.0, "{metrics:?}");
+        assert!(plane.ssim.unwrap() > 0.9, "{metrics:?}");
+        assert!(plane.ms_ssim.unwrap() > 0.9, "{metrics:?}");
+    }
+
+    // Only chroma subsampling causes larger differences
+    let ciede2000 = metrics.ciede2000.unwrap();
+    assert!(ciede2000.mean < 2.0, "{ciede2000:?}");
+
+    // Chroma planes of the I420 reference are compared at half resolution
+    let metrics = metrics::compare(&yuv, &reference).unwrap();
+    assert_eq!(metrics.planes.len(), 3);
+    assert!(metrics.planes[1].ms_ssim.is_none());
+
+    // Images with the same format & color are compared using their code values, a single changed luma sample
+    // doesn't leak into chroma
+    let mut changed = yuv.clone();
+    let (y_plane, _) = changed.planes_mut().next().unwrap();
+    y_plane[0] = y_plane[0].wrapping_add(1);
+
+    let metrics = metrics::compare(&yuv, &changed).unwrap();
+    let expected_psnr = 10.0 * (255.0f64 * 255.0 * (width * height) as f64).log10();
+
+    assert!(
+        (metrics.planes[0].psnr - expected_psnr).abs() < 1e-9,
+        "{metrics:?}"
+    );
+    assert!(metrics.planes[1..].iter().all(|p| p.psnr.is_infinite()));
+}
+
+#[test]
+fn image_statistics() {
+    let (width, height) = (8, 4);
+
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    // Y increases from left to right, U & V are constant
+    let mut data: Vec<u8> = (0..height)
+        .flat_map(|_| (0..width).map(|x| 16 + x as u8 * 10))
+        .collect();
+    data.extend([120u8; 8]);
+    data.extend([140u8; 8]);
+
+    let image = Image::from_buffer(PixelFormat::I420, data, None, width, height, color).unwrap();
+
+    let stats = statistics::native_statistics(&image, None).unwrap();
+    let channels: Vec<Channel> = stats.iter().map(|s| s.channel).collect();
+    assert_eq!(channels, [Channel::Y, Channel::U, Channel::V]);
+
+    assert_eq!(stats[0].histogram.len(), 256);
+    assert_eq!((stats[0].min, stats[0].max), (16.0, 86.0));
+    assert_eq!(stats[0].mean, 51.0);
+    assert_eq!(stats[0].histogram[16], 4);
+    assert_eq!(stats[1].histogram[120], 8);
+    assert_eq!(stats[2].mean, 140.0);
+
+    // Window starting at an odd column includes the chroma samples shared with the previous pixel
+    let window = Window {
+        x: 3,
+        y: 1,
+        width: 2,
+        height: 1,
+    };
+    let stats = statistics::native_statistics(&image, Some(window)).unwrap();
+    assert_eq!((stats[0].min, stats[0].max), (46.0, 56.0));
+    assert_eq!(stats[1].histogram.iter().sum::<u64>(), 2);
+
+    // Packed 4:2:2, the window covers the second luma sample of one group and the first one of the next
+    let yuyv: Vec<u8> = (0..2)
+        .flat_map(|_| (0..4u8).flat_map(|g| [20 * g, 100 + g, 20 * g + 10, 200 + g]))
+        .collect();
+    let yuyv = Image::from_buffer(PixelFormat::YUYV, yuyv, None, 8, 2, color).unwrap();
+
+    let stats = statistics::native_statistics(&yuyv, Some(window)).unwrap();
+    let ranges: Vec<(f32, f32)> = stats.iter().map(|s| (s.min, s.max)).collect();
+    assert_eq!(ranges, [(30.0, 40.0), (101.0, 102.0), (201.0, 202.0)]);
+    assert!(stats.iter().all(|s| s.histogram.iter().sum::<u64>() == 2));
+
+    let stats = statistics::rgb_statistics(&image, Some(window), 10).unwrap();
+    let channels: Vec<Channel> = stats.iter().map(|s| s.channel).collect();
+    assert_eq!(channels, [Channel::R, Channel::G, Channel::B, Channel::Y]);
+
+    for s in &stats {
+        assert_eq!(s.histogram.iter().sum::<u64>(), 2);
+        assert!(s.min <= s.max && (0.0..=1.0).contains(&s.min), "{s:?}");
+    }
+
+    let luma = &stats[3];
+    assert!((luma.min - 30.0 / 219.0).abs() < 0.01, "{luma:?}");
+    assert!((luma.max - 40.0 / 219.0).abs() < 0.01, "{luma:?}");
+
+    assert!(
+        statistics::native_statistics(
+            &image,
+            Some(Window {
+                x: 4,
+                y: 0,
+                width: 5,
+                height: 1,
+            })
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn rgb_statistics_window() {
+    let (width, height) = (100, 6);
+
+    // R increases from left to right, G from top to bottom and B is the inverse of R
+    let data: Vec<u8> = (0..height)
+        .flat_map(|y| (0..width).flat_map(move |x| [x as u8 * 2, y as u8 * 40, 255 - x as u8 * 2]))
+        .collect();
+
+    let image = Image::from_buffer(
+        PixelFormat::RGB,
+        data,
+        None,
+        width,
+        height,
+        ColorInfo::RGB(RgbColorInfo::new(
+            ColorTransfer::SRGB,
+            ColorPrimaries::BT709,
+        )),
+    )
+    .unwrap();
+
+    // Starts and ends inside of a vector on every backend
+    let window = Window {
+        x: 5,
+        y: 1,
+        width: 87,
+        height: 4,
+    };
+
+    let stats = statistics::rgb_statistics(&image, Some(window), 256).unwrap();
+
+    let xs = window.x..window.x + window.width;
+    let ys = window.y..window.y + window.height;
+    let count = (xs.len() * ys.len()) as u64;
+
+    let expected = [
+        (
+            xs.clone().map(|x| x as f64 * 2.0).collect::<Vec<_>>(),
+            10.0,
+            182.0,
+        ),
+        (ys.clone().map(|y| y as f64 * 40.0).collect(), 40.0, 160.0),
+        (
+            xs.clone().map(|x| 255.0 - x as f64 * 2.0).collect(),
+            73.0,
+            245.0,
+        ),
+    ];
+
+    for (s, (values, min, max)) in stats.iter().zip(expected) {
+        let mean = values.iter().sum::<f64>() / values.len() as f64 / 255.0;
+
+        assert!((s.min * 255.0 - min).abs() < 0.01, "{s:?}");
+        assert!((s.max * 255.0 - max).abs() < 0.01, "{s:?}");
+        assert!((s.mean - mean).abs() < 1e-4, "{s:?} {mean}");
+        assert_eq!(s.histogram.iter().sum::<u64>(), count);
+        assert_eq!(s.histogram[min as usize], count / values.len() as u64);
+    }
+
+    assert_eq!(stats[3].histogram.iter().sum::<u64>(), count);
+}
+
+#[test]
+fn content_light_level() {
+    let (width, height) = (16, 4);
+
+    let color = |transfer| {
+        ColorInfo::RGB(RgbColorInfo {
+            transfer,
+            primaries: ColorPrimaries::BT2020,
+            full_range: true,
+            premultiplied_alpha: false,
+        })
+    };
+
+    // Left half at the highest code value, right half black
+    let data: Vec<u8> = (0..height)
+        .flat_map(|_| (0..width).flat_map(|x| if x < width / 2 { [255; 3] } else { [0; 3] }))
+        .collect();
+
+    let pq = Image::from_buffer(
+        PixelFormat::RGB,
+        data.clone(),
+        None,
+        width,
+        height,
+        color(ColorTransfer::BT2100PQ),
+    )
+    .unwrap();
+
+    let level = light_level::content_light_level(&pq).unwrap();
+    assert!((level.max_cll - 10000.0).abs() < 1.0, "{level:?}");
+    assert!((level.max_fall - 5000.0).abs() < 1.0, "{level:?}");
+
+    let hlg = Image::from_buffer(
+        PixelFormat::RGB,
+        data,
+        None,
+        width,
+        height,
+        color(ColorTransfer::BT2100HLG),
+    )
+    .unwrap();
+
+    let mut accumulator = light_level::ContentLightLevelAccumulator::new();
+    let level = accumulator.add_frame(&hlg).unwrap();
+    assert!((level.max_cll - 1000.0).abs() < 1.0, "{level:?}");
+    assert!((level.max_fall - 500.0).abs() < 1.0, "{level:?}");
+
+    accumulator.add(light_level::ContentLightLevel {
+        max_cll: 400.0,
+        max_fall: 600.0,
+    });
+
+    let level = accumulator.content_light_level();
+    assert_eq!(accumulator.frames(), 2);
+    assert!((level.max_cll - 1000.0).abs() < 1.0, "{level:?}");
+    assert_eq!(level.max_fall, 600.0);
+
+    let sdr = Image::blank(PixelFormat::RGB, width, height, rgb_color(false));
+    assert!(light_level::content_light_level(&sdr).is_err());
+}
+
+#[test]
+fn scopes() {
+    let (width, height) = (16, 8);
+
+    // Left half white, right half black
+    let data: Vec<u8> = (0..height)
+        .flat_map(|_| (0..width).flat_map(|x| if x < width / 2 { [255; 3] } else { [0; 3] }))
+        .collect();
+
+    let src = Image::from_buffer(
+        PixelFormat::RGB,
+        data,
+        None,
+        width,
+        height,
+        rgb_color(false),
+    )
+    .unwrap();
+
+    let pixel = |image: &Image<Vec<u8>>, x: usize, y: usize| {
+        let (plane, stride) = image.planes().next().unwrap();
+        <[u8; 3]>::try_from(&plane[y * stride + x * 3..][..3]).unwrap()
+    };
+
+    let mut waveform = Image::blank(PixelFormat::RGB, 32, 16, rgb_color(false));
+    scopes::waveform(
+        &src,
+        &mut waveform,
+        ColorSpace::BT709,
+        scopes::WaveformMode::Luma,
+    )
+    .unwrap();
+
+    for x in 0..32 {
+        let (lit, dark) = if x < 16 { (0, 15) } else { (15, 0) };
+
+        assert!(pixel(&waveform, x, lit)[0] > 0, "{x}");
+        assert_eq!(pixel(&waveform, x, dark), [0, 0, 0], "{x}");
+        assert_eq!(pixel(&waveform, x, 8), [0, 0, 0], "{x}");
+    }
+
+    let mut vectorscope = Image::blank(PixelFormat::RGB, 16, 16, rgb_color(false));
+    scopes::vectorscope(&src, &mut vectorscope, ColorSpace::BT709).unwrap();
+
+    // Achromatic pixels end up in the center
+    let lit: Vec<(usize, usize)> = (0..16)
+        .flat_map(|y| (0..16).map(move |x| (x, y)))
+        .filter(|&(x, y)| pixel(&vectorscope, x, y) != [0, 0, 0])
+        .collect();
+
+    assert_eq!(lit, [(8, 8)]);
+
+    let mut false_color = Image::blank(PixelFormat::RGB, width, height, rgb_color(false));
+    scopes::false_color(
+        &src,
+        &mut false_color,
+        ColorSpace::BT709,
+        scopes::DEFAULT_FALSE_COLOR_ZONES,
+    )
+    .unwrap();
+
+    assert_eq!(pixel(&false_color, 0, 0), [255, 0, 0]);
+    assert_eq!(pixel(&false_color, width - 1, 0), [128, 0, 128]);
+
+    // Levels cannot be mapped to the rows or columns of an empty scope
+    for (scope_width, scope_height) in [(0, 0), (16, 0), (0, 16)] {
+        let mut empty = Image::blank(
+            PixelFormat::RGB,
+            scope_width,
+            scope_height,
+            rgb_color(false),
+        );
+
+        assert!(matches!(
+            scopes::waveform(
+                &src,
+                &mut empty,
+                ColorSpace::BT709,
+                scopes::WaveformMode::Luma
+            ),
+            Err(scopes::ScopeError::EmptyDestination)
+        ));
+        assert!(matches!(
+            scopes::vectorscope(&src, &mut empty, ColorSpace::BT709),
+            Err(scopes::ScopeError::EmptyDestination)
+        ));
+    }
+}
+
+#[test]
+fn rotate_and_flip() {
+    let (width, height) = (64, 32);
+
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut rgba = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+    fill_test_pattern(&mut rgba, TestPattern::SmpteBars).unwrap();
+
+    let pixel = |image: &Image<Vec<u8>>, x: usize, y: usize| {
+        let (plane, stride) = image.planes().next().unwrap();
+        plane[y * stride + x * 4..][..4].to_vec()
+    };
+
+    let transforms = [
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::Transverse,
+    ];
+
+    for format in PixelFormat::variants() {
+        let mut src = Image::blank(format, width, height, color);
+        convert(&rgba, &mut src).unwrap();
+
+        let mut src_rgba = Image::blank(PixelFormat::RGBA, width, height, rgb_color(false));
+        convert(&src, &mut src_rgba).unwrap();
+
+        for t in transforms {
+            let (w, h) = t.dimensions(width, height);
+
+            let mut expected = Image::blank(PixelFormat::RGBA, w, h, rgb_color(false));
+            transform(&src_rgba, &mut expected, t).unwrap();
+
+            // Converted to RGBA while transforming
+            let mut converted = Image::blank(PixelFormat::RGBA, w, h, rgb_color(false));
+            transform(&src, &mut converted, t).unwrap();
+
+            // Transformed without changing the format
+            let mut same = Image::blank(format, w, h, color);
+            transform(&src, &mut same, t).unwrap();
+
+            // Rotated 4:2:2 formats must resample the chroma of the bars' sharp edges
+            for (distorted, min_psnr) in [(&converted as &dyn ImageRef, 40.0), (&same, 20.0)] {
+                let metrics = metrics::compare(&expected, distorted).unwrap();
+
+                for plane in &metrics.planes[..3] {
+                    assert!(plane.psnr > min_psnr, "{t:?} {format:?} {metrics:?}");
+                }
+            }
+        }
+    }
+
+    let mut rotated = Image::blank(PixelFormat::RGBA, height, width, rgb_color(false));
+    transform(&rgba, &mut rotated, Transform::Rotate90).unwrap();
+
+    assert_eq!(pixel(&rotated, 0, 0), pixel(&rgba, 0, height - 1));
+    assert_eq!(pixel(&rotated, height - 1, 0), pixel(&rgba, 0, 0));
+    assert_eq!(
+        pixel(&rotated, 0, width - 1),
+        pixel(&rgba, width - 1, height - 1)
+    );
+
+    assert!(transform(&rgba, &mut rgba.clone(), Transform::Rotate90).is_err());
+}
+
+#[test]
+fn fill_and_clear() {
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut i420 = Image::blank(PixelFormat::I420, 8, 4, color);
+    clear(&mut i420, None).unwrap();
+
+    // Red in 8-bit full range RGB, only filling the right half
+    let window = Window {
+        x: 4,
+        y: 0,
+        width: 4,
+        height: 4,
+    };
+    fill(
+        &mut i420,
+        &rgb_color(false),
+        8,
+        [255.0, 0.0, 0.0],
+        Some(window),
+    )
+    .unwrap();
+
+    let planes: Vec<_> = i420.planes().collect();
+    let (y, y_stride) = planes[0];
+    let (u, _) = planes[1];
+    let (v, _) = planes[2];
+
+    assert_eq!(&y[y_stride..y_stride + 8], [16, 16, 16, 16, 63, 63, 63, 63]);
+    assert_eq!(&u[..4], [128, 128, 102, 102]);
+    assert_eq!(&v[..4], [128, 128, 240, 240]);
+
+    let mut p010 = Image::blank(PixelFormat::P010, 4, 2, color);
+    clear(&mut p010, None).unwrap();
+
+    let planes: Vec<_> = p010.planes().collect();
+    assert_eq!(&planes[0].0[..2], 64u16.to_ne_bytes());
+    assert_eq!(
+        &planes[1].0[..4],
+        [512u16.to_ne_bytes(), 512u16.to_ne_bytes()].concat()
+    );
+
+    // YUYV luma samples are written per pixel, chroma per pair of pixels
+    let mut yuyv = Image::blank(PixelFormat::YUYV, 4, 2, color);
+    clear(&mut yuyv, None).unwrap();
+    fill(
+        &mut yuyv,
+        &color,
+        8,
+        [235.0, 128.0, 128.0],
+        Some(Window {
+            x: 1,
+            y: 0,
+            width: 2,
+            height: 1,
+        }),
+    )
+    .unwrap();
+
+    let (plane, _) = yuyv.planes().next().unwrap();
+    assert_eq!(&plane[..8], [16, 128, 235, 128, 235, 128, 16, 128]);
+
+    let mut rgba = Image::blank(PixelFormat::RGBA, 2, 2, rgb_color(false));
+    fill(&mut rgba, &color, 8, [235.0, 128.0, 128.0], None).unwrap();
+
+    let (plane, _) = rgba.planes().next().unwrap();
+    assert!(plane.iter().all(|v| *v == 255));
+
+    assert!(fill(&mut rgba, &color, 8, [0.0; 3], Some(window)).is_err());
+}
+
+#[test]
+fn resize_fit() {
+    let rgb = rgb_color(false);
+
+    let pixel = |image: &Image<Vec<u8>>, x: usize, y: usize| {
+        let (plane, stride) = image.planes().next().unwrap();
+        plane[y * stride + x * 4..][..4].to_vec()
+    };
+
+    // 4:3 image with a red left and a green right half
+    let mut src = Image::blank(PixelFormat::RGBA, 40, 30, rgb);
+    fill(&mut src, &rgb, 8, [255.0, 0.0, 0.0], None).unwrap();
+    let right = Window {
+        x: 20,
+        y: 0,
+        width: 20,
+        height: 30,
+    };
+    fill(&mut src, &rgb, 8, [0.0, 255.0, 0.0], Some(right)).unwrap();
+
+    let mut resizer = Resizer::new(ResizeAlg::Nearest);
+
+    // Pillarbox into 16:9, scaled to 48x36 at x = 8
+    let mut dst = Image::blank(PixelFormat::RGBA, 64, 36, rgb);
+    resizer
+        .fit(&src, &mut dst, FitMode::Contain(Padding::Black))
+        .unwrap();
+
+    assert_eq!(pixel(&dst, 7, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel(&dst, 8, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(&dst, 55, 35), [0, 255, 0, 255]);
+    assert_eq!(pixel(&dst, 56, 35), [0, 0, 0, 255]);
+
+    // Cover a square, cropping 5 columns from both sides
+    let mut dst = Image::blank(PixelFormat::RGBA, 32, 32, rgb);
+    resizer.fit(&src, &mut dst, FitMode::Cover).unwrap();
+
+    assert_eq!(pixel(&dst, 15, 31), [255, 0, 0, 255]);
+    assert_eq!(pixel(&dst, 16, 31), [0, 255, 0, 255]);
+
+    // Letterbox I420 with blue padding, placement is aligned to the chroma subsampling
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let mut yuv_src = Image::blank(PixelFormat::I420, 40, 30, color);
+    convert(&src, &mut yuv_src).unwrap();
+
+    let mut yuv_dst = Image::blank(PixelFormat::I420, 40, 40, color);
+    let blue = Padding::Color {
+        color: rgb,
+        bits: 8,
+        value: [0.0, 0.0, 255.0],
+    };
+    resizer
+        .fit(&yuv_src, &mut yuv_dst, FitMode::Contain(blue))
+        .unwrap();
+
+    let mut rgba = Image::blank(PixelFormat::RGBA, 40, 40, rgb);
+    convert(&yuv_dst, &mut rgba).unwrap();
+
+    let is_blue = |p: Vec<u8>| p[0] < 8 && p[1] < 8 && p[2] > 247;
+
+    // Scaled to 40x30 at y = 4
+    assert!(is_blue(pixel(&rgba, 0, 3)));
+    assert!(pixel(&rgba, 0, 4)[0] > 250);
+    assert!(pixel(&rgba, 39, 33)[1] > 250);
+    assert!(is_blue(pixel(&rgba, 39, 34)));
+
+    assert_eq!(
+        resizer.fit(&src, &mut yuv_dst, FitMode::Cover),
+        Err(ResizeError::DifferentFormats(
+            PixelFormat::RGBA,
+            PixelFormat::I420
+        ))
+    );
+}
+
+#[test]
+fn resize_cropped() {
+    let rgb = rgb_color(false);
+
+    // Gray ramp with a value of 8 * x
+    let ramp: Vec<u8> = (0..32).flat_map(|x| [x * 8, x * 8, x * 8, 255]).collect();
+    let ramp = Image::from_buffer(PixelFormat::RGBA, ramp.repeat(2), None, 32, 2, rgb).unwrap();
+
+    let mut resizer = Resizer::new(ResizeAlg::Nearest);
+    let mut dst = Image::blank(PixelFormat::RGBA, 8, 2, rgb);
+
+    let mut zoom = |x: f64| {
+        let rect = SourceRect {
+            x,
+            y: 0.0,
+            width: 8.0,
+            height: 2.0,
+        };
+
+        resizer.resize_cropped(&ramp, &mut dst, rect).unwrap();
+
+        let (plane, _) = dst.planes().next().unwrap();
+        plane[..32].iter().step_by(4).copied().collect::<Vec<_>>()
+    };
+
+    assert_eq!(zoom(4.25), [32, 40, 48, 56, 64, 72, 80, 88]);
+    assert_eq!(zoom(4.75), [40, 48, 56, 64, 72, 80, 88, 96]);
+
+    let out_of_bounds = SourceRect {
+        x: 30.5,
+        y: 0.0,
+        width: 2.0,
+        height: 2.0,
+    };
+    assert_eq!(
+        resizer.resize_cropped(&ramp, &mut dst, out_of_bounds),
+        Err(ResizeError::InvalidSourceRect(out_of_bounds))
+    );
+
+    // Subsampled planes are cropped at the same position as the luma plane
+    let gradient: Vec<u8> = (0..64u8)
+        .flat_map(|y| (0..64u8).flat_map(move |x| [x * 4, 128, y * 4, 255]))
+        .collect();
+    let src = Image::from_buffer(PixelFormat::RGBA, gradient, None, 64, 64, rgb).unwrap();
+
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    let rect = SourceRect {
+        x: 10.5,
+        y: 7.25,
+        width: 40.0,
+        height: 30.0,
+    };
+
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(fir::FilterType::Bilinear));
+
+    let mut expected = Image::blank(PixelFormat::RGBA, 32, 24, rgb);
+    resizer.resize_cropped(&src, &mut expected, rect).unwrap();
+
+    for format in [PixelFormat::I420, PixelFormat::NV12, PixelFormat::YUYV] {
+        let mut yuv = Image::blank(format, 64, 64, color);
+        convert(&src, &mut yuv).unwrap();
+
+        let mut cropped = Image::blank(format, 32, 24, color);
+        resizer.resize_cropped(&yuv, &mut cropped, rect).unwrap();
+
+        let metrics = metrics::compare(&expected, &cropped).unwrap();
+
+        for plane in &metrics.planes[..3] {
+            assert!(plane.psnr > 38.0, "{format:?} {metrics:?}");
+        }
+    }
+}
+
+#[test]
+fn resize_bounds_check() {
+    /// Image whose planes are shorter than its dimensions require
+    struct Truncated(Image<Vec<u8>>);
+
+    unsafe impl ImageRef for Truncated {
+        fn format(&self) -> PixelFormat {
+            self.0.format()
+        }
+
+        fn width(&self) -> usize {
+            self.0.width()
+        }
+
+        fn height(&self) -> usize {
+            self.0.height()
+        }
+
+        fn planes(&self) -> Box<dyn Iterator<Item = (&[u8], usize)> + '_> {
+            Box::new(
+                self.0
+                    .planes()
+                    .map(|(plane, stride)| (&plane[..plane.len() / 2], stride)),
+            )
+        }
+
+        fn color(&self) -> ColorInfo {
+            self.0.color()
+        }
+    }
+
+    let rgb = rgb_color(false);
+    let src = Truncated(Image::blank(PixelFormat::RGBA, 16, 16, rgb));
+
+    let mut resizer = Resizer::new(ResizeAlg::Nearest);
+    let mut dst = Image::blank(PixelFormat::RGBA, 8, 8, rgb);
+
+    assert!(matches!(
+        resizer.resize(&src, &mut dst),
+        Err(ResizeError::BoundsCheck(_))
+    ));
+
+    let rect = SourceRect {
+        x: 0.0,
+        y: 0.0,
+        width: 16.0,
+        height: 16.0,
+    };
+    assert!(matches!(
+        resizer.resize_cropped(&src, &mut dst, rect),
+        Err(ResizeError::BoundsCheck(_))
+    ));
+}
+
+#[test]
+fn resize_chroma_siting() {
+    let color = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SDR,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: false,
+    });
+
+    // Luma and U follow the same ramp, with U sampled at the position of the left luma sample
+    let y_row: Vec<u8> = (0..32).map(|x| 16 + 6 * x).collect();
+    let u_row: Vec<u8> = (0..16).map(|x| 16 + 12 * x).collect();
+
+    let buffer = [y_row.repeat(4), u_row.repeat(2), vec![128; 32]].concat();
+    let src = Image::from_buffer(PixelFormat::I420, buffer, None, 32, 4, color).unwrap();
+
+    let resize = |siting: ChromaSiting| {
+        let mut dst = Image::blank(PixelFormat::I420, 16, 2, color);
+
+        Resizer::new(ResizeAlg::Convolution(fir::FilterType::Bilinear))
+            .with_chroma_siting(siting)
+            .resize(&src, &mut dst)
+            .unwrap();
+
+        let planes: Vec<_> = dst.planes().map(|(plane, _)| plane.to_vec()).collect();
+
+        // Difference of every (not at the edge) U sample to the luma sample at its position
+        (1..7)
+            .map(|x| i32::from(planes[1][x]) - i32::from(planes[0][x * 2]))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(resize(ChromaSiting::Left).iter().all(|d| d.abs() <= 1));
+    assert!(resize(ChromaSiting::Center).iter().all(|d| *d >= 2));
+}
+
+#[test]
+fn resize_linear_light() {
+    let rgb = ColorInfo::RGB(RgbColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        full_range: true,
+        premultiplied_alpha: false,
+    });
+
+    // Black and white checkerboard with 1 pixel squares
+    let checkerboard: Vec<u8> = (0..16)
+        .flat_map(|y| {
+            (0..16).flat_map(move |x| {
+                let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+                [v, v, v, 255]
+            })
+        })
+        .collect();
+    let checkerboard =
+        Image::from_buffer(PixelFormat::RGBA, checkerboard, None, 16, 16, rgb).unwrap();
+
+    let downscale = |src: &Image<Vec<u8>>, linear_light: bool| {
+        let mut dst = Image::blank(src.format(), 8, 8, src.color());
+
+        Resizer::new(ResizeAlg::Convolution(fir::FilterType::Box))
+            .with_linear_light(linear_light)
+            .resize(src, &mut dst)
+            .unwrap();
+
+        let mut rgba = Image::blank(PixelFormat::RGBA, 8, 8, rgb);
+        convert(&dst, &mut rgba).unwrap();
+
+        let (plane, stride) = rgba.planes().next().unwrap();
+        plane[3 * stride + 3 * 4..][..4].to_vec()
+    };
+
+    // Averaging the encoded values results in a too dark gray of 50% signal instead of 50% light
+    assert_eq!(downscale(&checkerboard, false)[..3], [128, 128, 128]);
+    assert_eq!(downscale(&checkerboard, true), [188, 188, 188, 255]);
+
+    let yuv = ColorInfo::YUV(YuvColorInfo {
+        transfer: ColorTransfer::SRGB,
+        primaries: ColorPrimaries::BT709,
+        space: ColorSpace::BT709,
+        full_range: true,
+    });
+
+    let mut i420 = Image::blank(PixelFormat::I420, 16, 16, yuv);
+    convert(&checkerboard, &mut i420).unwrap();
+
+    let gray = downscale(&i420, true);
+    assert!(gray[..3].iter().all(|v| v.abs_diff(188) <= 1), "{gray:?}");
+
+    // Pillarboxed into the center of a wider image
+    let mut dst = Image::blank(PixelFormat::RGBA, 24, 8, rgb);
+    Resizer::new(ResizeAlg::Convolution(fir::FilterType::Box))
+        .with_linear_light(true)
+        .fit(&checkerboard, &mut dst, FitMode::Contain(Padding::Black))
+        .unwrap();
+
+    let (plane, stride) = dst.planes().next().unwrap();
+    assert_eq!(
+        plane[7 * stride + 7 * 4..][..8],
+        [0, 0, 0, 255, 188, 188, 188, 255]
+    );
+    assert_eq!(
+        plane[7 * stride + 15 * 4..][..8],
+        [188, 188, 188, 255, 0, 0, 0, 255]
+    );
+}
+
+#[test]
+fn resize_linear_light_converts_color() {
+    let pq = ColorInfo::RGB(RgbColorInfo {
+        transfer: ColorTransfer::BT2100PQ,
+        primaries: ColorPrimaries::BT2020,
+        full_range: true,
+        premultiplied_alpha: false,
+    });
+
+    // Solid dark color inside of the SDR range, so resizing must give the same result as converting
+    let src = Image::from_buffer(
+        PixelFormat::RGBA,
+        [25u8, 20, 15, 255].repeat(8 * 8),
+        None,
+        8,
+        8,
+        pq,
+    )
+    .unwrap();
+
+    let mut expected = Image::blank(PixelFormat::RGBA, 8, 8, rgb_color(false));
+    convert(&src, &mut expected).unwrap();
+    let expected = expected.planes().next().unwrap().0[..4].to_vec();
+
+    let mut dst = Image::blank(PixelFormat::RGBA, 4, 4, rgb_color(false));
+    Resizer::new(ResizeAlg::Convolution(fir::FilterType::Bilinear))
+        .with_linear_light(true)
+        .resize(&src, &mut dst)
+        .unwrap();
+
+    for px in dst.planes().next().unwrap().0.chunks(4) {
+        for (a, b) in px.iter().zip(&expected) {
+            assert!(a.abs_diff(*b) <= 1, "{px:?} != {expected:?}");
+        }
+    }
+}
+
+#[test]
+fn resize_linear_light_fit_odd_scaled_size() {
+    let rgb = rgb_color(false);
+    let src =
+        Image::from_buffer(PixelFormat::RGBA, vec![255; 30 * 20 * 4], None, 30, 20, rgb).unwrap();
+
+    // Scaled to 40x26.67, which is aligned to an even height
+    let mut dst = Image::blank(PixelFormat::RGBA, 40, 40, rgb);
+    Resizer::new(ResizeAlg::Convolution(fir::FilterType::Bilinear))
+        .with_linear_light(true)
+        .fit(&src, &mut dst, FitMode::Contain(Padding::Black))
+        .unwrap();
+
+    let (plane, stride) = dst.planes().next().unwrap();
+    let pixel = |y: usize| &plane[y * stride + 20 * 4..][..4];
+
+    assert_eq!(pixel(5), [0, 0, 0, 255]);
+    assert_eq!(pixel(6), [255, 255, 255, 255]);
+    assert_eq!(pixel(31), [255, 255, 255, 255]);
+    assert_eq!(pixel(32), [0, 0, 0, 255]);
+}
+
+#[test]
+fn resize_linear_light_premultiplied() {
+    // Columns alternating between opaque red and fully transparent pixels
+    let data: Vec<u8> = (0..8 * 2)
+        .flat_map(|i| {
+            if i % 2 == 0 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 0, 0]
+            }
+        })
+        .collect();
+
+    for premultiplied_alpha in [false, true] {
+        let color = rgb_color(premultiplied_alpha);
+        let src = Image::from_buffer(PixelFormat::RGBA, data.clone(), None, 8, 2, color).unwrap();
+
+        let mut dst = Image::blank(PixelFormat::RGBA, 4, 2, color);
+        Resizer::new(ResizeAlg::Convolution(fir::FilterType::Box))
+            .with_linear_light(true)
+            .resize(&src, &mut dst)
+            .unwrap();
+
+        // The transparent pixels must not darken the red, only make it half transparent
+        for px in dst.planes().next().unwrap().0.chunks(4) {
+            let [r, g, b, a] = px.try_into().unwrap();
+            let expected_r = if premultiplied_alpha { a } else { 255 };
+
+            assert!(a.abs_diff(128) <= 1, "{premultiplied_alpha} {px:?}");
+            assert!(r.abs_diff(expected_r) <= 1, "{premultiplied_alpha} {px:?}");
+            assert_eq!([g, b], [0, 0], "{premultiplied_alpha} {px:?}");
+        }
+    }
+}